@@ -47,6 +47,15 @@ impl<Balance> XToken<Balance> {
         }
     }
 
+    pub fn decimals(&self) -> u8 {
+        match self {
+            XToken::NEP141(_, _, _, _, decimals)
+            | XToken::ERC20(_, _, _, _, decimals)
+            | XToken::BEP20(_, _, _, _, decimals) => *decimals,
+            XToken::FND10(_, _) => crate::constants::STANDARD_DECIMALS,
+        }
+    }
+
     pub fn contract(&self) -> Vec<u8> {
         match self {
             XToken::NEP141(_, contract, _, _, _)
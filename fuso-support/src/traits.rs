@@ -44,6 +44,9 @@ pub trait Token<AccountId> {
 
     fn is_stable(token_id: &Self::TokenId) -> bool;
 
+    /// the minimal unit of a token after its amount unified to `STANDARD_DECIMALS`
+    fn precision_tolerance(token_id: &Self::TokenId) -> Self::Balance;
+
     fn free_balance(token: &Self::TokenId, who: &AccountId) -> Self::Balance;
 
     fn total_issuance(token: &Self::TokenId) -> Self::Balance;
@@ -152,7 +152,7 @@ pub mod pallet {
     {
         #[pallet::weight(195_000_0000)]
         pub fn native_limit(origin: OriginFor<T>, value: bool) -> DispatchResult {
-            ensure_root(origin)?;
+            let _ = Self::ensure_admin(origin)?;
             <NativeCheck<T>>::put(value);
             Ok(())
         }
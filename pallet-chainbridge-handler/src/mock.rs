@@ -20,7 +20,7 @@ pub use frame_support::{
     weights::{IdentityFee, Weight},
     PalletId, StorageValue,
 };
-use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use sp_runtime::{traits::AccountIdConversion, AccountId32, Permill};
 
 pub(crate) type BlockNumber = u32;
 pub type Signature = MultiSignature;
@@ -189,22 +189,47 @@ parameter_types! {
     pub const DominatorCheckGracePeriod: BlockNumber = 1;
     pub const MaxMakerFee: u32 = 10000;
     pub const MaxTakerFee: u32 = 10000;
+    pub const AuthorizingExpiry: BlockNumber = 100;
+    pub const CommissionUpdateInterval: BlockNumber = 100;
+    pub const MaxCommission: Permill = Permill::from_percent(50);
+    pub const RedelegateCooldown: BlockNumber = 100;
+    pub const UnbondingDelay: BlockNumber = 14400 * 4;
+    pub const RootCheckpointInterval: u64 = 3;
+    pub const MaxRootCheckpoints: u32 = 2;
+    pub const RenameFee: Balance = 100;
+    pub const X25519KeyRotationDelay: BlockNumber = 10;
+    pub const BookkeepingWeightLimit: Weight = Weight::from_ref_time(u64::MAX);
 }
 
 impl pallet_fuso_verifier::Config for Test {
     type Asset = Assets;
+    type AuthorizingExpiry = AuthorizingExpiry;
+    type BookkeepingWeightLimit = BookkeepingWeightLimit;
     type BrokerStakingThreshold = BrokerStakingThreshold;
     type Callback = RuntimeCall;
+    type CommissionUpdateInterval = CommissionUpdateInterval;
+    type DominatorAdminOrigin = frame_system::EnsureRoot<AccountId>;
     type DominatorCheckGracePeriod = DominatorCheckGracePeriod;
     type DominatorOnlineThreshold = DominatorOnlineThreshold;
     type Indicator = ();
+    type MarketAdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type MaxCommission = MaxCommission;
     type MaxMakerFee = MaxMakerFee;
+    type MaxRootCheckpoints = MaxRootCheckpoints;
     type MaxTakerFee = MaxTakerFee;
     type MinimalStakingAmount = MinimalStakingAmount;
+    type PauseOrigin = frame_system::EnsureRoot<AccountId>;
+    type RedelegateCooldown = RedelegateCooldown;
+    type RenameFee = RenameFee;
     type Rewarding = PhantomData;
+    type RootCheckpointInterval = RootCheckpointInterval;
     type RuntimeEvent = RuntimeEvent;
     type SeasonDuration = SeasonDuration;
+    type SweepOrigin = frame_system::EnsureRoot<AccountId>;
+    type TreasuryAccount = TreasuryAccount;
+    type UnbondingDelay = UnbondingDelay;
     type WeightInfo = ();
+    type X25519KeyRotationDelay = X25519KeyRotationDelay;
 }
 
 pub type AssetBalance = u128;
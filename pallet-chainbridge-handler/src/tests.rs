@@ -799,3 +799,18 @@ fn transfer_out_charge_unstable_non_native() {
         ))]);
     })
 }
+
+#[test]
+fn native_limit_should_check_origin() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            ChainBridgeTransfer::native_limit(RuntimeOrigin::signed(RELAYER_A), true),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(ChainBridgeTransfer::native_limit(
+            RuntimeOrigin::signed(TREASURY),
+            true
+        ));
+        assert!(ChainBridgeTransfer::native_check());
+    });
+}
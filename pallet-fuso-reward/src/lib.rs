@@ -57,6 +57,9 @@ pub mod pallet {
 
         #[pallet::constant]
         type RewardTerminateAt: Get<Self::BlockNumber>;
+
+        /// the origin allowed to update the era rewards
+        type RewardAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
     }
 
     #[pallet::event]
@@ -126,7 +129,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             era_rewards: Balance<T>,
         ) -> DispatchResultWithPostInfo {
-            let _ = ensure_root(origin)?;
+            T::RewardAdminOrigin::ensure_origin(origin)?;
             EraRewards::<T>::put(era_rewards);
             Self::deposit_event(Event::EraRewardsUpdated(era_rewards));
             Ok(().into())
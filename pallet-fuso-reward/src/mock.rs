@@ -119,6 +119,7 @@ parameter_types! {
 impl pallet_fuso_reward::Config for Test {
     type Asset = TokenModule;
     type EraDuration = EraDuration;
+    type RewardAdminOrigin = EnsureSignedBy<TreasuryMembers, AccountId>;
     type RewardTerminateAt = RewardTerminateAt;
     type RewardsPerEra = RewardsPerEra;
     type RuntimeEvent = RuntimeEvent;
@@ -1,4 +1,4 @@
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 use fuso_support::traits::Rewarding;
 use sp_keyring::AccountKeyring;
 
@@ -60,3 +60,20 @@ fn test_reward_should_work() {
         assert_eq!(vol, 20000);
     });
 }
+
+#[test]
+fn test_set_era_rewards_should_check_origin() {
+    new_test_ext().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let treasury = AccountId::new([5u8; 32]);
+        assert_noop!(
+            RewardModule::set_era_rewards(RuntimeOrigin::signed(alice), 100),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(RewardModule::set_era_rewards(
+            RuntimeOrigin::signed(treasury),
+            100
+        ));
+        assert_eq!(RewardModule::era_rewards(), 100);
+    });
+}
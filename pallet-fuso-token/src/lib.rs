@@ -50,7 +50,7 @@ pub mod pallet {
     use scale_info::TypeInfo;
     use sp_runtime::traits::{
         AtLeast32BitUnsigned, CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, One,
-        StaticLookup, Zero,
+        Saturating, StaticLookup, Zero,
     };
     use sp_runtime::DispatchResult;
     use sp_std::vec::Vec;
@@ -189,7 +189,7 @@ pub mod pallet {
 
         #[pallet::weight(0)]
         pub fn mark_stable(origin: OriginFor<T>, id: T::TokenId) -> DispatchResultWithPostInfo {
-            let _ = ensure_root(origin)?;
+            let _ = T::AdminOrigin::ensure_origin(origin)?;
             Tokens::<T>::try_mutate_exists(id, |info| -> DispatchResult {
                 ensure!(info.is_some(), Error::<T>::InvalidToken);
                 let mut token_info = info.take().unwrap();
@@ -349,6 +349,55 @@ pub mod pallet {
         }
     }
 
+    impl<T: Config> Pallet<T> {
+        /// the free and reserved balances, the native token is included
+        pub fn balance_of(token: T::TokenId, who: T::AccountId) -> (BalanceOf<T>, BalanceOf<T>) {
+            (
+                <Self as Token<T::AccountId>>::free_balance(&token, &who),
+                <Self as ReservableToken<T::AccountId>>::reserved_balance(&token, &who),
+            )
+        }
+
+        pub fn balances_of(who: T::AccountId) -> Vec<(T::TokenId, BalanceOf<T>, BalanceOf<T>)> {
+            Self::tokens()
+                .into_iter()
+                .map(|token| {
+                    let (free, reserved) = Self::balance_of(token, who.clone());
+                    (token, free, reserved)
+                })
+                .filter(|(_, free, reserved)| !free.is_zero() || !reserved.is_zero())
+                .collect()
+        }
+
+        /// `(symbol, contract, total, stable, decimals, chain_id)` of the issued token
+        pub fn token_info_of(
+            token: T::TokenId,
+        ) -> Option<(Vec<u8>, Vec<u8>, BalanceOf<T>, bool, u8, ChainId)> {
+            Self::get_token_info(&token).map(|info| {
+                (
+                    info.symbol(),
+                    info.contract(),
+                    <Self as Token<T::AccountId>>::total_issuance(&token),
+                    info.is_stable(),
+                    info.decimals(),
+                    Self::chain_id_of(&info),
+                )
+            })
+        }
+
+        /// the native token followed by the issued tokens
+        pub fn tokens() -> Vec<T::TokenId> {
+            let mut tokens = Tokens::<T>::iter_keys().collect::<Vec<_>>();
+            tokens.sort();
+            tokens.insert(0, T::NativeTokenId::get());
+            tokens
+        }
+
+        pub fn token_by_contract(chain_id: ChainId, contract: Vec<u8>) -> Option<T::TokenId> {
+            Self::get_token_from_chainbridge((chain_id, contract))
+        }
+    }
+
     impl<T: Config> fungibles::Inspect<T::AccountId> for Pallet<T> {
         type AssetId = T::TokenId;
         type Balance = BalanceOf<T>;
@@ -600,6 +649,18 @@ pub mod pallet {
             }
         }
 
+        fn precision_tolerance(token: &T::TokenId) -> Self::Balance {
+            let decimals = if *token == Self::native_token_id() {
+                STANDARD_DECIMALS
+            } else {
+                Self::get_token_info(token)
+                    .map(|t| t.decimals())
+                    .unwrap_or(STANDARD_DECIMALS)
+            };
+            let radix: BalanceOf<T> = 10u32.into();
+            radix.saturating_pow(STANDARD_DECIMALS.saturating_sub(decimals) as usize)
+        }
+
         fn free_balance(token: &T::TokenId, who: &T::AccountId) -> Self::Balance {
             if *token == Self::native_token_id() {
                 return pallet_balances::Pallet::<T>::free_balance(who);
@@ -301,3 +301,91 @@ fn alter_total_issuance_should_work() {
         );
     });
 }
+
+#[test]
+fn precision_tolerance_should_follow_decimals() {
+    let usdt = XToken::NEP141(
+        br#"USDT"#.to_vec(),
+        br#"usdt.testnet"#.to_vec(),
+        Zero::zero(),
+        true,
+        6,
+    );
+    let wnear = XToken::NEP141(
+        br#"WNEAR"#.to_vec(),
+        br#"wrap.testnet"#.to_vec(),
+        Zero::zero(),
+        false,
+        24,
+    );
+    new_test_ext().execute_with(|| {
+        assert_ok!(Token::issue(RuntimeOrigin::signed(TREASURY), usdt));
+        assert_ok!(Token::issue(RuntimeOrigin::signed(TREASURY), wnear));
+        assert_eq!(Token::precision_tolerance(&1), 1000000000000);
+        assert_eq!(Token::precision_tolerance(&2), 1);
+        assert_eq!(Token::precision_tolerance(&Token::native_token_id()), 1);
+    });
+}
+
+#[test]
+fn mark_stable_should_check_origin() {
+    let wnear = XToken::NEP141(
+        br#"WNEAR"#.to_vec(),
+        br#"wrap.testnet"#.to_vec(),
+        Zero::zero(),
+        false,
+        24,
+    );
+    new_test_ext().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        assert_ok!(Token::issue(RuntimeOrigin::signed(TREASURY), wnear));
+        assert_noop!(
+            Token::mark_stable(RuntimeOrigin::signed(alice), 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert!(!Token::is_stable(&1));
+        assert_ok!(Token::mark_stable(RuntimeOrigin::signed(TREASURY), 1));
+        assert!(Token::is_stable(&1));
+    });
+}
+
+#[test]
+fn runtime_api_queries_should_work() {
+    let usdt = XToken::ERC20(
+        br#"USDT"#.to_vec(),
+        [1u8; 20].to_vec(),
+        Zero::zero(),
+        true,
+        6,
+    );
+    new_test_ext().execute_with(|| {
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        assert_ok!(Token::issue(RuntimeOrigin::signed(TREASURY), usdt));
+        assert_eq!(Token::tokens(), vec![0, 1]);
+        assert_eq!(
+            Token::token_info_of(1),
+            Some((br#"USDT"#.to_vec(), [1u8; 20].to_vec(), 0, true, 6, 1))
+        );
+        assert_eq!(Token::token_info_of(2), None);
+        assert!(Token::balances_of(ferdie.clone()).is_empty());
+
+        assert_ok!(Token::do_mint(1, &ferdie, 2000000, None));
+        assert_ok!(Token::reserve(&1, &ferdie, ONE));
+        assert_eq!(Token::balance_of(1, ferdie.clone()), (ONE, ONE));
+        assert_eq!(Token::balances_of(ferdie.clone()), vec![(1, ONE, ONE)]);
+        assert_eq!(
+            Token::balance_of(0, TREASURY),
+            (Balances::free_balance(&TREASURY), 0)
+        );
+        assert_eq!(Token::token_info_of(1).unwrap().2, 2 * ONE);
+
+        assert_eq!(Token::token_by_contract(1, b"usdt".to_vec()), None);
+        assert_ok!(Token::associate_token(
+            RuntimeOrigin::signed(TREASURY),
+            1,
+            b"usdt".to_vec(),
+            1
+        ));
+        assert_eq!(Token::token_by_contract(1, b"usdt".to_vec()), Some(1));
+    });
+}
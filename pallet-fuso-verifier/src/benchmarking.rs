@@ -1,12 +1,13 @@
 use super::*;
 use crate::Pallet as Verifier;
 pub use frame_benchmarking::{account, benchmarks};
+use frame_support::{traits::EnsureOrigin, weights::Weight};
 use frame_system::RawOrigin;
 use fuso_support::constants::*;
 use fuso_support::external_chain::XToken;
 use sp_runtime::traits::CheckedAdd;
 use sp_runtime::traits::StaticLookup;
-use sp_runtime::DispatchResult;
+use sp_runtime::{DispatchError, DispatchResult, Permill};
 
 pub type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
 
@@ -26,6 +27,7 @@ benchmarks! {
         T::BlockNumber: Into<u32> + From<u32>,
         T: pallet_balances::Config,
         T: pallet_fuso_token::Config,
+        T::Callback: From<frame_system::Call<T>>,
     }
 
     register {
@@ -41,13 +43,14 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
         Verifier::<T>::stake(
             <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
             dominator.clone(),
             (100_000 * DOLLARS).into()
         )?;
-    }: evict(RawOrigin::Root, dominator)
+        let origin = T::DominatorAdminOrigin::successful_origin();
+    }: evict<T::RuntimeOrigin>(origin, dominator)
 
     launch {
         frame_system::Pallet::<T>::set_block_number(3000.into());
@@ -57,7 +60,8 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-    }: _(RawOrigin::Root, dominator)
+        let origin = T::DominatorAdminOrigin::successful_origin();
+    }: _<T::RuntimeOrigin>(origin, dominator)
 
     stake {
         frame_system::Pallet::<T>::set_block_number(3000.into());
@@ -67,7 +71,7 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
     } :_(RawOrigin::Signed(ben), dominator, (100_000 * DOLLARS).into())
 
     unstake {
@@ -78,7 +82,7 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
         Verifier::<T>::stake(
             <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
             dominator.clone(),
@@ -95,7 +99,7 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
         Verifier::<T>::stake(
             <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
             dominator.clone(),
@@ -133,7 +137,7 @@ benchmarks! {
             (100_000 * DOLLARS).into(),
             None
         )?;
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
         Verifier::<T>::stake(
             <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
             dominator.clone(),
@@ -150,7 +154,7 @@ benchmarks! {
             b"cool".to_vec()
         )?;
         let dominator = T::Lookup::unlookup(ben.clone());
-        Verifier::<T>::launch(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Root), dominator.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
         pallet_fuso_token::Pallet::<T>::issue(
             <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(lance.clone())),
                XToken::NEP141(
@@ -199,6 +203,275 @@ benchmarks! {
         )?;
        Receipts::<T>::remove(ben.clone(), lance.clone());
     } :_(RawOrigin::Signed(lance), dominator, 1u32.into(), (50_000 * DOLLARS).into())
+
+    reclaim_authorizing {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        let lance: T::AccountId = account("Lance", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        let dominator = T::Lookup::unlookup(ben.clone());
+        pallet_fuso_token::Pallet::<T>::issue(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(lance.clone())),
+            XToken::NEP141(
+                br#"USDT"#.to_vec(),
+                br#"usdt.testnet"#.to_vec(),
+                0u32.into(),
+                true,
+                6
+            )
+        )?;
+        pallet_fuso_token::Pallet::<T>::do_mint(
+            1u32.into(),
+            &lance,
+            (100_000 * DOLLARS).into(),
+            None
+        )?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+        Verifier::<T>::authorize(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(lance.clone())),
+            dominator.clone(),
+            1u32.into(),
+            (50_000 * DOLLARS).into(),
+        )?;
+        frame_system::Pallet::<T>::set_block_number(T::AuthorizingExpiry::get() + 3000.into());
+    } :_(RawOrigin::Signed(lance), dominator)
+
+    retry_callback {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let lance: T::AccountId = account("Lance", 0, SEED);
+        let callback: T::Callback = frame_system::Call::<T>::remark { remark: vec![] }.into();
+        FailedCallbacks::<T>::insert(&lance, 0, FailedCallback {
+            callback,
+            error: DispatchError::Other("benchmark"),
+            failed_at: 3000.into(),
+        });
+    } :_(RawOrigin::Signed(lance), 0, Weight::from_ref_time(u64::MAX))
+
+    discard_callback {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let lance: T::AccountId = account("Lance", 0, SEED);
+        let callback: T::Callback = frame_system::Call::<T>::remark { remark: vec![] }.into();
+        FailedCallbacks::<T>::insert(&lance, 0, FailedCallback {
+            callback,
+            error: DispatchError::Other("benchmark"),
+            failed_at: 3000.into(),
+        });
+    } :_(RawOrigin::Signed(lance), 0)
+
+    redelegate {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        let lance: T::AccountId = account("Lance", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(lance.clone())),
+            b"fine".to_vec()
+        )?;
+        let from = T::Lookup::unlookup(ben.clone());
+        let to = T::Lookup::unlookup(lance.clone());
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), from.clone())?;
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), to.clone())?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            from.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(lance.clone())),
+            to.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+    } :_(RawOrigin::Signed(ben), from, to, (50_000 * DOLLARS).into())
+
+    rebond {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        let dominator = T::Lookup::unlookup(ben.clone());
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+        Verifier::<T>::unstake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (50_000 * DOLLARS).into()
+        )?;
+    } :_(RawOrigin::Signed(ben), dominator, (50_000 * DOLLARS).into())
+
+    claim_all_shares {
+        let c in 1 .. 10;
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        for i in 0 .. c {
+            let dominator: T::AccountId = account("Dominator", i, SEED);
+            let mut name = b"dominator".to_vec();
+            name.push(b'a' + i as u8);
+            Verifier::<T>::register(
+                <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(dominator.clone())),
+                name
+            )?;
+            let dominator = T::Lookup::unlookup(dominator);
+            Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+            Verifier::<T>::stake(
+                <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+                dominator,
+                (10_000 * DOLLARS).into()
+            )?;
+        }
+        frame_system::Pallet::<T>::set_block_number(T::SeasonDuration::get() + 3000.into());
+    } :_(RawOrigin::Signed(ben), c, None)
+
+    snapshot_dominator {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        let dominator = T::Lookup::unlookup(ben.clone());
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+        let now = T::SeasonDuration::get() + 3000.into();
+        let dominator = Dominators::<T>::get(&ben).unwrap();
+    }: {
+        Verifier::<T>::snapshot_dominator(&ben, &dominator, now);
+    }
+
+    unlock_unbonding {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        let dominator = T::Lookup::unlookup(ben.clone());
+        Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+        Verifier::<T>::stake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (100_000 * DOLLARS).into()
+        )?;
+        Verifier::<T>::unstake(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            dominator.clone(),
+            (50_000 * DOLLARS).into()
+        )?;
+        let unlock_at = T::UnbondingDelay::get() + 3000.into();
+    }: {
+        Verifier::<T>::unlock_unbonding(unlock_at, ben.clone(), (50_000 * DOLLARS).into());
+    }
+
+    migrate_authorization {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        for (i, name) in [b"cool", b"fine"].into_iter().enumerate() {
+            let dominator: T::AccountId = account("Dominator", i as u32, SEED);
+            Verifier::<T>::register(
+                <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(dominator.clone())),
+                name.to_vec()
+            )?;
+            let dominator = T::Lookup::unlookup(dominator);
+            Verifier::<T>::launch(T::DominatorAdminOrigin::successful_origin(), dominator.clone())?;
+            Verifier::<T>::stake(
+                <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+                dominator,
+                (100_000 * DOLLARS).into()
+            )?;
+        }
+        let from: T::AccountId = account("Dominator", 0, SEED);
+        let to: T::AccountId = account("Dominator", 1, SEED);
+        let token_id: TokenId<T> = 0u32.into();
+        Reserves::<T>::insert(&(RESERVE_FOR_AUTHORIZING, ben.clone(), token_id), &from, Balance::<T>::from(100 * DOLLARS));
+    }: _(RawOrigin::Signed(ben), T::Lookup::unlookup(from), T::Lookup::unlookup(to), token_id, (100 * DOLLARS).into())
+
+    set_pause {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        let dominator = T::Lookup::unlookup(ben.clone());
+        let origin = T::PauseOrigin::successful_origin();
+    }: _<T::RuntimeOrigin>(origin, Some(dominator), PAUSE_ALL)
+
+    rename {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+    }: _(RawOrigin::Signed(ben), b"fine".to_vec())
+
+    set_pubkey {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        Verifier::<T>::dominator_set_pubkey(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            [1u8; 32].to_vec()
+        )?;
+    }: dominator_set_pubkey(RawOrigin::Signed(ben), [2u8; 32].to_vec())
+
+    set_commission {
+        frame_system::Pallet::<T>::set_block_number(3000.into());
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        Verifier::<T>::register(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            b"cool".to_vec()
+        )?;
+        Verifier::<T>::dominator_set_commission(
+            <T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(ben.clone())),
+            Permill::zero()
+        )?;
+        frame_system::Pallet::<T>::set_block_number(T::CommissionUpdateInterval::get() + 3000.into());
+    }: dominator_set_commission(RawOrigin::Signed(ben), T::MaxCommission::get())
+
+    set_payee {
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        let payee: T::AccountId = account("Payee", 0, SEED);
+    }: _(RawOrigin::Signed(ben), Some(T::Lookup::unlookup(payee)))
+
+    set_auto_compound {
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        let payee: T::AccountId = account("Payee", 0, SEED);
+    }: _(RawOrigin::Signed(ben), Some(T::Lookup::unlookup(payee)))
+
+    sweep_dust {
+        let ben: T::AccountId = account("Ben", 0, SEED);
+        let token_id: TokenId<T> = 0u32.into();
+        Dusts::<T>::insert(&ben, token_id, Dust {
+            surplus: (100 * DOLLARS).into(),
+            deficit: (10 * DOLLARS).into(),
+        });
+        let dominator = T::Lookup::unlookup(ben.clone());
+        let origin = T::SweepOrigin::successful_origin();
+    }: _<T::RuntimeOrigin>(origin, dominator, token_id)
 }
 // verify {
 //     let mut proof_raws = vec![
@@ -41,13 +41,17 @@ pub mod pallet {
         traits::{PriceOracle, ReservableToken, Rewarding, Token},
     };
     use scale_info::TypeInfo;
-    use sp_core::sr25519::{Public as Sr25519Public, Signature as Sr25519Signature};
+    use sp_core::{
+        sr25519::{Public as Sr25519Public, Signature as Sr25519Signature},
+        U256,
+    };
     use sp_io::hashing::blake2_256 as hashing;
     use sp_runtime::{
         traits::{
-            AccountIdConversion, CheckedAdd, CheckedSub, StaticLookup, TrailingZeroInput, Zero,
+            AccountIdConversion, CheckedAdd, CheckedSub, Saturating, StaticLookup,
+            TrailingZeroInput, Zero,
         },
-        Permill, Perquintill, RuntimeDebug,
+        Permill, RuntimeDebug,
     };
     use sp_std::{
         collections::btree_map::BTreeMap, convert::*, prelude::*, result::Result, vec::Vec,
@@ -62,8 +66,23 @@ pub mod pallet {
     pub type Amount = u128;
     pub type MerkleHash = [u8; 32];
     pub const PALLET_ID: frame_support::PalletId = frame_support::PalletId(*b"fuso/vrf");
-    const UNSTAKE_DELAY_BLOCKS: u32 = 14400 * 4u32;
+    /// `authorize` and the migrations into the dominator
+    pub const PAUSE_AUTHORIZE: u8 = 0b01;
+    /// the trading commands relayed by the brokers
+    pub const PAUSE_TRADE: u8 = 0b10;
+    /// the revokes and the proofs of the commands already sequenced by the dominators are never
+    /// paused so the users could always escape
+    pub const PAUSE_ALL: u8 = PAUSE_AUTHORIZE | PAUSE_TRADE;
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(8);
+    const REWARD_PER_STAKE_SCALE: u128 = 1_000_000_000_000_000_000u128;
     const MAX_PROOF_SIZE: usize = 10 * 1024 * 1024usize;
+    /// the proofs submitted at once, the compressed ones are charged by this before decoding
+    const MAX_PROOFS_PER_BATCH: u32 = 100;
+    const QUINTILL: u128 = 1_000_000_000_000_000_000;
+    /// the offchain matchers keep 7 decimals at most, even for the tokens with more decimals
+    const MIN_CLEARING_TOLERANCE: u128 = 100_000_000_000;
+    const MAX_RPC_ENDPOINTS: usize = 4;
+    const MAX_RPC_ENDPOINT_LEN: usize = 512;
 
     #[derive(Clone, Eq, PartialEq, RuntimeDebug)]
     pub struct Trade<TokenId, Balance> {
@@ -221,6 +240,27 @@ pub mod pallet {
         RevokeWithCallback(TokenId, Balance, BlockNumber, Callback),
     }
 
+    #[derive(Clone, Encode, Decode, RuntimeDebug, Eq, PartialEq, TypeInfo)]
+    pub struct X25519Key<BlockNumber> {
+        pub version: u32,
+        pub key: Vec<u8>,
+        pub activate_at: BlockNumber,
+    }
+
+    #[derive(Clone, Encode, Decode, RuntimeDebug, Eq, PartialEq, TypeInfo)]
+    pub struct RootCheckpoint<BlockNumber> {
+        pub event_id: u64,
+        pub block_number: BlockNumber,
+        pub root: MerkleHash,
+    }
+
+    #[derive(Clone, Encode, Decode, RuntimeDebug, Eq, PartialEq, TypeInfo)]
+    pub struct FailedCallback<Callback, BlockNumber> {
+        pub callback: Callback,
+        pub error: DispatchError,
+        pub failed_at: BlockNumber,
+    }
+
     #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
     pub struct Dominator<Balance, BlockNumber> {
         pub name: Vec<u8>,
@@ -235,21 +275,62 @@ pub mod pallet {
     pub struct Staking<Balance> {
         from_season: Season,
         amount: Balance,
+        /// stake × blocks of `from_season`, assuming the `amount` is kept until the season ends
+        weighted: u128,
     }
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
     pub struct Bonus<TokenId, Balance> {
+        /// the total staking when the season ends
         pub staked: Balance,
+        /// stake × blocks of the season
+        pub weighted: u128,
         pub profit: BTreeMap<TokenId, Balance>,
     }
 
+    /// the profit per stake × block of a finalized season, scaled by `REWARD_PER_STAKE_SCALE`
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
+    pub struct SeasonReward<TokenId> {
+        pub per_stake: BTreeMap<TokenId, u128>,
+        /// the accumulated `per_stake` of all seasons until this one
+        pub accumulated: BTreeMap<TokenId, u128>,
+    }
+
+    /// the rounding differences between the confirmed and the proven balances during clearing
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
+    pub struct Dust<Balance> {
+        /// lost by the users, could be swept to the treasury
+        pub surplus: Balance,
+        /// gained by the users
+        pub deficit: Balance,
+    }
+
+    #[derive(Decode)]
+    struct StakingV0<Balance> {
+        from_season: Season,
+        amount: Balance,
+    }
+
+    #[derive(Decode)]
+    struct BonusV0<TokenId, Balance> {
+        staked: Balance,
+        profit: BTreeMap<TokenId, Balance>,
+    }
+
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
     pub struct DominatorSetting<AccountId> {
         pub beneficiary: Option<AccountId>,
+        /// deprecated and always empty, the x25519 keys are read from `X25519Keys`
         pub x25519_pubkey: Vec<u8>,
         pub rpc_endpoint: Vec<u8>,
     }
 
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
+    pub struct Commission<BlockNumber> {
+        pub rate: Permill,
+        pub updated_at: BlockNumber,
+    }
+
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, Default)]
     pub struct Broker<AccountId, Balance, BlockNumber> {
         pub beneficiary: AccountId,
@@ -263,6 +344,8 @@ pub mod pallet {
         from_season: Season,
         to_season: Season,
         staking: Balance<T>,
+        weighted: u128,
+        compound: bool,
     }
 
     #[pallet::config]
@@ -303,6 +386,61 @@ pub mod pallet {
 
         #[pallet::constant]
         type MaxTakerFee: Get<u32>;
+
+        /// an `Authorize` receipt not processed by the dominator within this period could be reclaimed
+        #[pallet::constant]
+        type AuthorizingExpiry: Get<Self::BlockNumber>;
+
+        /// the minimal interval between two commission updates of a dominator
+        #[pallet::constant]
+        type CommissionUpdateInterval: Get<Self::BlockNumber>;
+
+        /// the maximal commission rate a dominator could charge
+        #[pallet::constant]
+        type MaxCommission: Get<Permill>;
+
+        /// the minimal interval between two redelegations of a staker
+        #[pallet::constant]
+        type RedelegateCooldown: Get<Self::BlockNumber>;
+
+        /// the unbonding delay must be 1 * period, 2 * period... of `DominatorCheckGracePeriod`
+        #[pallet::constant]
+        type UnbondingDelay: Get<Self::BlockNumber>;
+
+        /// the maximum weight of season snapshots and unstake unlocks in `on_initialize`
+        #[pallet::constant]
+        type BookkeepingWeightLimit: Get<Weight>;
+
+        /// the account receiving the swept dust
+        #[pallet::constant]
+        type TreasuryAccount: Get<Self::AccountId>;
+
+        /// a merkle root is checkpointed at least every `RootCheckpointInterval` events of a dominator
+        #[pallet::constant]
+        type RootCheckpointInterval: Get<u64>;
+
+        /// the maximum checkpoints of merkle roots kept for a dominator
+        #[pallet::constant]
+        type MaxRootCheckpoints: Get<u32>;
+
+        type PauseOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// the origin allowed to launch or evict dominators
+        type DominatorAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// the origin allowed to sweep the dust of dominators to the `TreasuryAccount`
+        type SweepOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// the origin allowed to approve the non-stable quote currencies
+        type MarketAdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// the native tokens charged for renaming a dominator, transferred to the `TreasuryAccount`
+        #[pallet::constant]
+        type RenameFee: Get<Balance<Self>>;
+
+        /// the grace period before a rotated x25519 key of a dominator takes effect
+        #[pallet::constant]
+        type X25519KeyRotationDelay: Get<Self::BlockNumber>;
     }
 
     #[pallet::storage]
@@ -317,6 +455,36 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// the historical merkle roots of a dominator, the oldest is dropped once full
+    #[pallet::storage]
+    #[pallet::getter(fn root_checkpoints)]
+    pub type RootCheckpoints<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Vec<RootCheckpoint<T::BlockNumber>>,
+        ValueQuery,
+    >;
+
+    /// the current x25519 key of a dominator and the next one if it is rotating
+    #[pallet::storage]
+    #[pallet::getter(fn x25519_keys)]
+    pub type X25519Keys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<X25519Key<T::BlockNumber>>, ValueQuery>;
+
+    /// the destination dominators of the `Revoke` receipts which should be authorized to it directly
+    #[pallet::storage]
+    #[pallet::getter(fn pending_migrations)]
+    pub type PendingMigrations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        T::AccountId,
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn dominators)]
     pub type Dominators<T: Config> = StorageMap<
@@ -327,11 +495,39 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// the lowercased dominator names to the dominators
+    #[pallet::storage]
+    #[pallet::getter(fn dominator_by_name)]
+    pub type DominatorByName<T: Config> =
+        StorageMap<_, Blake2_128Concat, Vec<u8>, T::AccountId, OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn dominator_settings)]
     pub type DominatorSettings<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, DominatorSetting<T::AccountId>, OptionQuery>;
 
+    /// the dominators updating their rpc endpoints in the current block, so the brokers could
+    /// refresh their caches without decoding the events
+    #[pallet::storage]
+    #[pallet::getter(fn rpc_endpoint_updates)]
+    pub type RpcEndpointUpdates<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn dominator_commissions)]
+    pub type DominatorCommissions<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Commission<T::BlockNumber>, OptionQuery>;
+
+    /// the raised commission and the block it takes effect, i.e. the start of the next season
+    #[pallet::storage]
+    #[pallet::getter(fn pending_commissions)]
+    pub type PendingCommissions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        (Commission<T::BlockNumber>, T::BlockNumber),
+        OptionQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn brokers)]
     pub type Brokers<T: Config> = StorageMap<
@@ -342,6 +538,29 @@ pub mod pallet {
         OptionQuery,
     >;
 
+    /// the `PAUSE_*` flags applied to all dominators
+    #[pallet::storage]
+    #[pallet::getter(fn global_pause)]
+    pub type GlobalPause<T: Config> = StorageValue<_, u8, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn dominator_pauses)]
+    pub type DominatorPauses<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u8, ValueQuery>;
+
+    /// the sum of `RESERVE_FOR_AUTHORIZING` of all users by dominator and token
+    #[pallet::storage]
+    #[pallet::getter(fn authorized_totals)]
+    pub type AuthorizedTotals<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        TokenId<T>,
+        Balance<T>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn reserves)]
     pub type Reserves<T: Config> = StorageDoubleMap<
@@ -378,6 +597,92 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// the dominators staked by a staker, i.e. the reversed index of `Stakings`
+    #[pallet::storage]
+    #[pallet::getter(fn staked_dominators)]
+    pub type StakedDominators<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        (),
+        OptionQuery,
+    >;
+
+    /// the number of stakings whose `from_season` is the season
+    #[pallet::storage]
+    #[pallet::getter(fn staking_counts)]
+    pub type StakingCounts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Season,
+        u32,
+        ValueQuery,
+    >;
+
+    /// the finalized seasons which are still the `from_season` of some stakings
+    #[pallet::storage]
+    #[pallet::getter(fn season_rewards)]
+    pub type SeasonRewards<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Season,
+        SeasonReward<TokenId<T>>,
+        OptionQuery,
+    >;
+
+    /// the accumulated profit per stake × block of a dominator until the latest finalized season
+    #[pallet::storage]
+    #[pallet::getter(fn accumulated_rewards)]
+    pub type AccumulatedRewards<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, BTreeMap<TokenId<T>, u128>, ValueQuery>;
+
+    /// the block until which the stake × blocks of a dominator has been accumulated into `Bonuses`
+    #[pallet::storage]
+    #[pallet::getter(fn staking_checkpoints)]
+    pub type StakingCheckpoints<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
+    /// stakers who restake the native shares automatically, other shares go to the payee
+    #[pallet::storage]
+    #[pallet::getter(fn auto_compounds)]
+    pub type AutoCompounds<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// the accounts receiving the shares of stakers
+    #[pallet::storage]
+    #[pallet::getter(fn payees)]
+    pub type Payees<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+    /// the non-stable quote currencies approved for each base currency
+    #[pallet::storage]
+    #[pallet::getter(fn approved_quotes)]
+    pub type ApprovedQuotes<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, TokenId<T>, Twox64Concat, TokenId<T>, bool, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn dusts)]
+    pub type Dusts<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        TokenId<T>,
+        Dust<Balance<T>>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn last_redelegations)]
+    pub type LastRedelegations<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, T::BlockNumber, OptionQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn pending_unstakings)]
     pub type PendingUnstakings<T: Config> = StorageDoubleMap<
@@ -390,6 +695,42 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// the pending unstakings indexed by staker
+    #[pallet::storage]
+    #[pallet::getter(fn unbondings)]
+    pub type Unbondings<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::BlockNumber,
+        Balance<T>,
+        ValueQuery,
+    >;
+
+    /// the check block of the ongoing snapshot pass and the last visited key of `Dominators`
+    #[pallet::storage]
+    pub type SeasonCursor<T: Config> = StorageValue<_, (T::BlockNumber, Vec<u8>), OptionQuery>;
+
+    /// the next unlock block of `PendingUnstakings` to be processed
+    #[pallet::storage]
+    pub type UnlockCursor<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn failed_callbacks)]
+    pub type FailedCallbacks<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Twox64Concat,
+        u32,
+        FailedCallback<T::Callback, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    pub type NextFailedCallbackId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -410,6 +751,38 @@ pub mod pallet {
         DominatorInactive(T::AccountId),
         DominatorX25519KeyUpdated(T::AccountId, Vec<u8>),
         DominatorRpcEndpointUpdated(T::AccountId, Vec<u8>),
+        AuthorizingRefunded(T::AccountId, T::AccountId, TokenId<T>, Balance<T>),
+        CallbackFailed(T::AccountId, u32, DispatchError),
+        CallbackRetried(T::AccountId, u32),
+        CallbackDiscarded(T::AccountId, u32),
+        DominatorCommissionUpdated(T::AccountId, Permill),
+        AutoCompoundUpdated(T::AccountId, Option<T::AccountId>),
+        SharesCompounded(T::AccountId, T::AccountId, Balance<T>),
+        TaoRedelegated(T::AccountId, T::AccountId, T::AccountId, Balance<T>),
+        TaoRebonded(T::AccountId, T::AccountId, Balance<T>),
+        PayeeUpdated(T::AccountId, Option<T::AccountId>),
+        DustSwept(T::AccountId, TokenId<T>, Balance<T>),
+        QuoteApproved(TokenId<T>, TokenId<T>, bool),
+        AuthorizationMigrated(
+            T::AccountId,
+            T::AccountId,
+            T::AccountId,
+            TokenId<T>,
+            Balance<T>,
+        ),
+        AuthorizationMigrationAborted(
+            T::AccountId,
+            T::AccountId,
+            T::AccountId,
+            TokenId<T>,
+            Balance<T>,
+        ),
+        PauseUpdated(Option<T::AccountId>, u8),
+        DominatorRenamed(T::AccountId, Vec<u8>),
+        DominatorX25519KeyScheduled(T::AccountId, u32, Vec<u8>, T::BlockNumber),
+        TransferInExpired(T::AccountId, T::AccountId, TokenId<T>, Balance<T>),
+        DominatorCommissionScheduled(T::AccountId, Permill, T::BlockNumber),
+        AllSharesClaimed(T::AccountId, Option<T::AccountId>),
     }
 
     #[pallet::error]
@@ -442,11 +815,27 @@ pub mod pallet {
         BrokerNotFound,
         BrokerAlreadyRegistered,
         InvalidBeneficiaryProof,
+        AuthorizingExpired,
+        AuthorizingNotExpired,
+        CallbackNotExists,
+        CallbackWeightTooLow,
+        CommissionUpdateTooFrequent,
+        InvalidRedelegation,
+        RedelegateTooFrequent,
+        InsufficientUnbonding,
+        NoDustToSweep,
+        InvalidMigration,
+        CommandPaused,
+        InvalidX25519Key,
+        CommissionTooHigh,
+        QuoteNotPriced,
+        InvalidRpcEndpoint,
     }
 
     #[pallet::pallet]
     #[pallet::without_storage_info]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
@@ -456,43 +845,64 @@ pub mod pallet {
         Balance<T>: Copy + From<u128> + Into<u128>,
         T::BlockNumber: Into<u32> + From<u32>,
     {
-        /// save total staking of previous season
+        /// snapshot the seasons and unlock the pending unstakings within `BookkeepingWeightLimit`,
+        /// the rest will be continued in the later blocks
         fn on_initialize(now: T::BlockNumber) -> Weight {
-            let mut weight: Weight = Weight::from_ref_time(0u64);
-            if now % T::DominatorCheckGracePeriod::get() != Zero::zero() {
-                return weight;
+            let limit = T::BookkeepingWeightLimit::get();
+            let mut weight = RocksDbWeight::get().reads(3u64);
+            if RpcEndpointUpdates::<T>::exists() {
+                RpcEndpointUpdates::<T>::kill();
+                weight = weight.saturating_add(RocksDbWeight::get().writes(1u64));
             }
-            for (id, dominator) in Dominators::<T>::iter() {
-                let start = dominator.start_from;
-                if now == start {
-                    continue;
-                }
-                weight = weight.saturating_add(RocksDbWeight::get().reads(1u64));
-                if (now - start) % T::SeasonDuration::get() == Zero::zero() {
-                    let prv_season = ((now - start) / T::SeasonDuration::get()).into() - 1;
-                    Bonuses::<T>::mutate(id, prv_season, |b| b.staked = dominator.staked);
-                    weight = weight.saturating_add(RocksDbWeight::get().writes(1u64))
-                }
+            if now % T::DominatorCheckGracePeriod::get() == Zero::zero()
+                && !SeasonCursor::<T>::exists()
+            {
+                SeasonCursor::<T>::put((now, Vec::<u8>::new()));
+                weight = weight.saturating_add(RocksDbWeight::get().writes(1u64));
             }
-            for (staker, amount) in PendingUnstakings::<T>::drain_prefix(&now) {
-                let r = Self::unreserve(
-                    RESERVE_FOR_PENDING_UNSTAKE,
-                    staker.clone(),
-                    T::Asset::native_token_id(),
-                    amount,
-                    &Self::system_account(),
-                );
-                weight = weight.saturating_add(RocksDbWeight::get().writes(2u64));
-                if r.is_err() {
-                    log::error!(
-                        "No enough tokens of {:?} to unlock, check onchain storage.",
-                        staker
-                    );
-                } else {
-                    Self::deposit_event(Event::TaoUnstakeUnlock(staker.clone(), amount.clone()));
-                }
+            weight = weight.saturating_add(Self::progress_snapshots(limit.saturating_sub(weight)));
+            weight.saturating_add(Self::progress_unlocks(now, limit.saturating_sub(weight)))
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let version = StorageVersion::get::<Pallet<T>>();
+            let mut weight = Weight::from_ref_time(0u64);
+            if version < 1 {
+                weight = weight.saturating_add(Self::migrate_to_weighted_staking());
+            }
+            if version < 2 {
+                weight = weight.saturating_add(Self::migrate_to_indexed_unbondings());
+            }
+            if version < 3 {
+                weight = weight.saturating_add(Self::migrate_to_accumulated_rewards());
+            }
+            if version < 4 {
+                weight = weight.saturating_add(Self::migrate_to_authorized_totals());
             }
-            weight.saturating_add(RocksDbWeight::get().writes(1u64))
+            if version < 5 {
+                weight = weight.saturating_add(Self::migrate_to_indexed_names());
+            }
+            if version < 6 {
+                weight = weight.saturating_add(Self::migrate_to_versioned_x25519_keys());
+            }
+            if version < 7 {
+                weight = weight.saturating_add(Self::migrate_to_staked_dominators());
+            }
+            if version < 8 {
+                weight = weight.saturating_add(Self::clear_legacy_x25519_keys());
+            }
+            if version < STORAGE_VERSION {
+                STORAGE_VERSION.put::<Pallet<T>>();
+            }
+            weight
+        }
+
+        fn integrity_test() {
+            // the unlocks are only scanned at the boundaries of `DominatorCheckGracePeriod`
+            assert!(
+                T::UnbondingDelay::get() % T::DominatorCheckGracePeriod::get() == Zero::zero(),
+                "UnbondingDelay must be a multiple of DominatorCheckGracePeriod"
+            );
         }
     }
 
@@ -507,10 +917,7 @@ pub mod pallet {
         #[pallet::weight(<T as Config>::WeightInfo::register())]
         pub fn register(origin: OriginFor<T>, identifier: Vec<u8>) -> DispatchResultWithPostInfo {
             let dominator = ensure_signed(origin)?;
-            let name = AsciiStr::from_ascii(&identifier);
-            ensure!(name.is_ok(), Error::<T>::InvalidName);
-            let name = name.unwrap();
-            ensure!(name.len() >= 2 && name.len() <= 32, Error::<T>::InvalidName);
+            let name = Self::normalize_name(&identifier)?;
             let current_block = frame_system::Pallet::<T>::block_number();
             ensure!(
                 current_block >= T::DominatorCheckGracePeriod::get(),
@@ -521,12 +928,11 @@ pub mod pallet {
                 Error::<T>::DominatorAlreadyExists
             );
             ensure!(
-                Dominators::<T>::iter()
-                    .find(|d| &d.1.name == &identifier)
-                    .is_none(),
+                !DominatorByName::<T>::contains_key(&name),
                 Error::<T>::InvalidName
             );
             let register_at = current_block - current_block % T::DominatorCheckGracePeriod::get();
+            DominatorByName::<T>::insert(&name, &dominator);
             Dominators::<T>::insert(
                 &dominator,
                 Dominator {
@@ -542,12 +948,47 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// rename the dominator, charging `RenameFee`
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::rename())]
+        pub fn rename(origin: OriginFor<T>, identifier: Vec<u8>) -> DispatchResultWithPostInfo {
+            let dominator = ensure_signed(origin)?;
+            let name = Self::normalize_name(&identifier)?;
+            if let Some(owner) = DominatorByName::<T>::get(&name) {
+                ensure!(owner == dominator, Error::<T>::InvalidName);
+            }
+            let old = Dominators::<T>::try_mutate_exists(
+                &dominator,
+                |d| -> Result<Vec<u8>, DispatchError> {
+                    let d = d.as_mut().ok_or(Error::<T>::DominatorNotFound)?;
+                    ensure!(d.status != DOMINATOR_EVICTED, Error::<T>::DominatorEvicted);
+                    Ok(sp_std::mem::replace(&mut d.name, identifier.clone()))
+                },
+            )?;
+            let old = Self::normalize_name(&old).unwrap_or(old);
+            let fee = T::RenameFee::get();
+            if !fee.is_zero() {
+                T::Asset::transfer_token(
+                    &dominator,
+                    T::Asset::native_token_id(),
+                    fee,
+                    &T::TreasuryAccount::get(),
+                )?;
+            }
+            if DominatorByName::<T>::get(&old).as_ref() == Some(&dominator) {
+                DominatorByName::<T>::remove(&old);
+            }
+            DominatorByName::<T>::insert(&name, &dominator);
+            Self::deposit_event(Event::DominatorRenamed(dominator, identifier));
+            Ok(().into())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::evict())]
         pub fn evict(
             origin: OriginFor<T>,
             dominator_id: <T::Lookup as StaticLookup>::Source,
         ) -> DispatchResultWithPostInfo {
-            let _ = ensure_root(origin)?;
+            T::DominatorAdminOrigin::ensure_origin(origin)?;
             let dominator = T::Lookup::lookup(dominator_id)?;
             Dominators::<T>::try_mutate_exists(&dominator, |d| -> DispatchResult {
                 ensure!(d.is_some(), Error::<T>::DominatorNotFound);
@@ -565,7 +1006,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             dominator_id: <T::Lookup as StaticLookup>::Source,
         ) -> DispatchResultWithPostInfo {
-            let _ = ensure_root(origin)?;
+            T::DominatorAdminOrigin::ensure_origin(origin)?;
             let dominator = T::Lookup::lookup(dominator_id)?;
             Dominators::<T>::try_mutate_exists(&dominator, |d| -> DispatchResult {
                 ensure!(d.is_some(), Error::<T>::DominatorNotFound);
@@ -582,7 +1023,9 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::update_setting())]
+        /// the first key takes effect immediately, the later ones are activated after
+        /// `X25519KeyRotationDelay` while the current key is still valid
+        #[pallet::weight(<T as Config>::WeightInfo::set_pubkey())]
         pub fn dominator_set_pubkey(
             origin: OriginFor<T>,
             key: Vec<u8>,
@@ -592,25 +1035,41 @@ pub mod pallet {
                 Dominators::<T>::contains_key(&dominator),
                 Error::<T>::DominatorNotFound
             );
-            DominatorSettings::<T>::try_mutate(&dominator, |d| -> DispatchResult {
-                match d {
-                    Some(setting) => {
-                        setting.x25519_pubkey = key.clone();
-                    }
-                    None => {
-                        d.replace(DominatorSetting {
-                            beneficiary: None,
-                            x25519_pubkey: key.clone(),
-                            rpc_endpoint: vec![],
-                        });
-                    }
+            ensure!(key.len() == 32, Error::<T>::InvalidX25519Key);
+            let now = frame_system::Pallet::<T>::block_number();
+            let mut keys = Self::x25519_keys_at(&dominator, now);
+            match keys.first() {
+                Some(current) => {
+                    let next = X25519Key {
+                        version: current.version + 1,
+                        key: key.clone(),
+                        activate_at: now + T::X25519KeyRotationDelay::get(),
+                    };
+                    keys.truncate(1);
+                    keys.push(next.clone());
+                    Self::deposit_event(Event::DominatorX25519KeyScheduled(
+                        dominator.clone(),
+                        next.version,
+                        key,
+                        next.activate_at,
+                    ));
                 }
-                Ok(())
-            })?;
-            Self::deposit_event(Event::DominatorX25519KeyUpdated(dominator, key));
+                None => {
+                    keys.push(X25519Key {
+                        version: 0,
+                        key: key.clone(),
+                        activate_at: now,
+                    });
+                    Self::deposit_event(Event::DominatorX25519KeyUpdated(dominator.clone(), key));
+                }
+            }
+            X25519Keys::<T>::insert(&dominator, keys);
             Ok(().into())
         }
 
+        /// the `rpc_endpoint` is the primary `ws://` or `wss://` endpoint optionally followed by
+        /// the secondaries, separated by comma, e.g. `wss://a.example.com,wss://b.example.com`,
+        /// or empty to unpublish
         #[pallet::weight(<T as Config>::WeightInfo::update_setting())]
         pub fn dominator_set_rpc_endpoint(
             origin: OriginFor<T>,
@@ -621,6 +1080,7 @@ pub mod pallet {
                 Dominators::<T>::contains_key(&dominator),
                 Error::<T>::DominatorNotFound
             );
+            Self::validate_rpc_endpoint(&rpc_endpoint)?;
             DominatorSettings::<T>::try_mutate(&dominator, |d| -> DispatchResult {
                 match d {
                     Some(setting) => {
@@ -636,6 +1096,7 @@ pub mod pallet {
                 }
                 Ok(())
             })?;
+            RpcEndpointUpdates::<T>::append(&dominator);
             Self::deposit_event(Event::DominatorRpcEndpointUpdated(dominator, rpc_endpoint));
             Ok(().into())
         }
@@ -674,6 +1135,51 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// the commission is charged from both base and quote fees before sharing to the stakers,
+        /// a raised commission takes effect from the next season while a lowered one immediately
+        #[pallet::weight(<T as Config>::WeightInfo::set_commission())]
+        pub fn dominator_set_commission(
+            origin: OriginFor<T>,
+            rate: Permill,
+        ) -> DispatchResultWithPostInfo {
+            let dominator = ensure_signed(origin)?;
+            let d = Dominators::<T>::get(&dominator).ok_or(Error::<T>::DominatorNotFound)?;
+            ensure!(
+                rate <= T::MaxCommission::get(),
+                Error::<T>::CommissionTooHigh
+            );
+            let current_block = frame_system::Pallet::<T>::block_number();
+            Self::apply_pending_commission(&dominator, current_block);
+            let last_update = PendingCommissions::<T>::get(&dominator)
+                .map(|(c, _)| c.updated_at)
+                .or_else(|| DominatorCommissions::<T>::get(&dominator).map(|c| c.updated_at));
+            if let Some(updated_at) = last_update {
+                ensure!(
+                    current_block >= updated_at + T::CommissionUpdateInterval::get(),
+                    Error::<T>::CommissionUpdateTooFrequent
+                );
+            }
+            let commission = Commission {
+                rate,
+                updated_at: current_block,
+            };
+            if rate <= Self::commission_of(&dominator, current_block) {
+                PendingCommissions::<T>::remove(&dominator);
+                DominatorCommissions::<T>::insert(&dominator, commission);
+                Self::deposit_event(Event::DominatorCommissionUpdated(dominator, rate));
+            } else {
+                let season = Self::current_season(current_block, d.start_from);
+                let effective_at = Self::start_block_of_season(d.start_from, season + 1);
+                PendingCommissions::<T>::insert(&dominator, (commission, effective_at));
+                Self::deposit_event(Event::DominatorCommissionScheduled(
+                    dominator,
+                    rate,
+                    effective_at,
+                ));
+            }
+            Ok(().into())
+        }
+
         #[pallet::weight(<T as Config>::WeightInfo::register())]
         pub fn register_broker(
             origin: OriginFor<T>,
@@ -719,7 +1225,11 @@ pub mod pallet {
             Ok(().into())
         }
 
-        #[pallet::weight((<T as Config>::WeightInfo::verify(), DispatchClass::Normal, Pays::No))]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::verify(MAX_PROOFS_PER_BATCH),
+            DispatchClass::Normal,
+            Pays::No
+        ))]
         pub fn verify_compress(
             origin: OriginFor<T>,
             compressed_proofs: Vec<u8>,
@@ -743,7 +1253,11 @@ pub mod pallet {
             Self::verify_batch(dominator_id, &dominator, proofs)
         }
 
-        #[pallet::weight((<T as Config>::WeightInfo::verify(), DispatchClass::Normal, Pays::No))]
+        #[pallet::weight((
+            <T as Config>::WeightInfo::verify(proofs.len() as u32),
+            DispatchClass::Normal,
+            Pays::No
+        ))]
         pub fn verify(
             origin: OriginFor<T>,
             proofs: Vec<Proof<T::AccountId>>,
@@ -798,27 +1312,252 @@ pub mod pallet {
                 dominator.status != DOMINATOR_REGISTERED,
                 Error::<T>::DominatorStatusInvalid
             );
-            let staking =
-                Stakings::<T>::try_get(&dex, &signer).map_err(|_| Error::<T>::InvalidStaking)?;
-            let current_block = frame_system::Pallet::<T>::block_number();
-            let current_season = Self::current_season(current_block, dominator.start_from);
-            let distribution = Distribution {
-                from_season: staking.from_season,
-                to_season: current_season,
-                staking: staking.amount,
-            };
-            Stakings::<T>::try_mutate(&dex, &signer, |s| -> DispatchResult {
-                Ok(s.from_season = Self::take_shares(&signer, &dex, &distribution)?)
-            })?;
+            Self::claim_shares_of(&signer, &dex, &dominator)?;
             Ok(().into())
         }
 
-        #[pallet::weight(<T as Config>::WeightInfo::authorize())]
-        pub fn authorize(
+        /// claim the shares of all dominators staked by the signer, at most `max_claims` dominators
+        /// after `start_after`, the last visited dominator is emitted to continue from if there may
+        /// be more
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::claim_all_shares(*max_claims))]
+        pub fn claim_all_shares(
             origin: OriginFor<T>,
-            dominator: <T::Lookup as StaticLookup>::Source,
-            token_id: TokenId<T>,
-            amount: Balance<T>,
+            max_claims: u32,
+            start_after: Option<T::AccountId>,
+        ) -> DispatchResultWithPostInfo {
+            let signer = ensure_signed(origin)?;
+            let staked = match start_after {
+                Some(ref last) => StakedDominators::<T>::iter_key_prefix_from(
+                    &signer,
+                    StakedDominators::<T>::hashed_key_for(&signer, last),
+                ),
+                None => StakedDominators::<T>::iter_key_prefix(&signer),
+            }
+            .take(max_claims as usize)
+            .collect::<Vec<_>>();
+            let visited = staked.len() as u32;
+            let last = (visited == max_claims)
+                .then(|| staked.last().cloned())
+                .flatten();
+            for dex in staked {
+                let dominator = match Dominators::<T>::get(&dex) {
+                    Some(dominator) if dominator.status != DOMINATOR_REGISTERED => dominator,
+                    _ => continue,
+                };
+                Self::claim_shares_of(&signer, &dex, &dominator)?;
+            }
+            Self::deposit_event(Event::AllSharesClaimed(signer, last));
+            Ok(Some(<T as Config>::WeightInfo::claim_all_shares(visited)).into())
+        }
+
+        /// the shares are sent to `payee` instead of the staker, or reset by `None`
+        #[pallet::weight(<T as Config>::WeightInfo::set_payee())]
+        pub fn set_payee(
+            origin: OriginFor<T>,
+            payee: Option<<T::Lookup as StaticLookup>::Source>,
+        ) -> DispatchResultWithPostInfo {
+            let staker = ensure_signed(origin)?;
+            let payee = payee.map(T::Lookup::lookup).transpose()?;
+            match payee {
+                Some(ref payee) => Payees::<T>::insert(&staker, payee),
+                None => Payees::<T>::remove(&staker),
+            }
+            Self::deposit_event(Event::PayeeUpdated(staker, payee));
+            Ok(().into())
+        }
+
+        /// replace the `PAUSE_*` flags of a dominator, or of all dominators by `None`
+        #[pallet::weight(<T as Config>::WeightInfo::set_pause())]
+        pub fn set_pause(
+            origin: OriginFor<T>,
+            dominator: Option<<T::Lookup as StaticLookup>::Source>,
+            flags: u8,
+        ) -> DispatchResultWithPostInfo {
+            T::PauseOrigin::ensure_origin(origin)?;
+            ensure!(flags & !PAUSE_ALL == 0, Error::<T>::IllegalParameters);
+            let dominator = dominator.map(T::Lookup::lookup).transpose()?;
+            match dominator {
+                Some(ref dominator) => {
+                    ensure!(
+                        Dominators::<T>::contains_key(dominator),
+                        Error::<T>::DominatorNotFound
+                    );
+                    if flags == 0 {
+                        DominatorPauses::<T>::remove(dominator);
+                    } else {
+                        DominatorPauses::<T>::insert(dominator, flags);
+                    }
+                }
+                None => GlobalPause::<T>::put(flags),
+            }
+            Self::deposit_event(Event::PauseUpdated(dominator, flags));
+            Ok(().into())
+        }
+
+        /// allow or disallow the market `base`/`quote` whose quote currency isn't stable,
+        /// the quote currency must have been priced by the indicator before approved
+        #[pallet::weight(<T as Config>::WeightInfo::update_setting())]
+        pub fn approve_quote(
+            origin: OriginFor<T>,
+            base: TokenId<T>,
+            quote: TokenId<T>,
+            approved: bool,
+        ) -> DispatchResultWithPostInfo {
+            T::MarketAdminOrigin::ensure_origin(origin)?;
+            ensure!(base != quote, Error::<T>::IllegalParameters);
+            if approved {
+                ensure!(
+                    !T::Indicator::get_price(&quote).is_zero(),
+                    Error::<T>::QuoteNotPriced
+                );
+                ApprovedQuotes::<T>::insert(base, quote, true);
+            } else {
+                ApprovedQuotes::<T>::remove(base, quote);
+            }
+            Self::deposit_event(Event::QuoteApproved(base, quote, approved));
+            Ok(().into())
+        }
+
+        /// transfer the surplus dust of a dominator to the treasury
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::sweep_dust())]
+        pub fn sweep_dust(
+            origin: OriginFor<T>,
+            dominator: <T::Lookup as StaticLookup>::Source,
+            token_id: TokenId<T>,
+        ) -> DispatchResultWithPostInfo {
+            T::SweepOrigin::ensure_origin(origin)?;
+            let dominator = T::Lookup::lookup(dominator)?;
+            let swept = Dusts::<T>::try_mutate(
+                &dominator,
+                token_id,
+                |dust| -> Result<Balance<T>, DispatchError> {
+                    let swept = dust.surplus.saturating_sub(dust.deficit);
+                    ensure!(!swept.is_zero(), Error::<T>::NoDustToSweep);
+                    dust.surplus = dust.deficit;
+                    Ok(swept)
+                },
+            )?;
+            T::Asset::try_mutate_account(
+                &token_id,
+                &T::TreasuryAccount::get(),
+                |b| -> DispatchResult {
+                    b.0 = b.0.checked_add(&swept).ok_or(Error::<T>::Overflow)?;
+                    Ok(())
+                },
+            )?;
+            Self::deposit_event(Event::DustSwept(dominator, token_id, swept));
+            Ok(().into())
+        }
+
+        /// restake the native shares automatically and send other shares to `payee`,
+        /// or disable it by `None`
+        #[pallet::weight(<T as Config>::WeightInfo::set_auto_compound())]
+        pub fn set_auto_compound(
+            origin: OriginFor<T>,
+            payee: Option<<T::Lookup as StaticLookup>::Source>,
+        ) -> DispatchResultWithPostInfo {
+            let staker = ensure_signed(origin)?;
+            let payee = payee.map(T::Lookup::lookup).transpose()?;
+            match payee {
+                Some(ref payee) => AutoCompounds::<T>::insert(&staker, payee),
+                None => AutoCompounds::<T>::remove(&staker),
+            }
+            Self::deposit_event(Event::AutoCompoundUpdated(staker, payee));
+            Ok(().into())
+        }
+
+        /// move the staking between active dominators without unbonding
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::redelegate())]
+        pub fn redelegate(
+            origin: OriginFor<T>,
+            from: <T::Lookup as StaticLookup>::Source,
+            to: <T::Lookup as StaticLookup>::Source,
+            amount: Balance<T>,
+        ) -> DispatchResultWithPostInfo {
+            let staker = ensure_signed(origin)?;
+            let from = T::Lookup::lookup(from)?;
+            let to = T::Lookup::lookup(to)?;
+            ensure!(from != to, Error::<T>::InvalidRedelegation);
+            let from_dominator =
+                Dominators::<T>::try_get(&from).map_err(|_| Error::<T>::DominatorNotFound)?;
+            ensure!(
+                from_dominator.status == DOMINATOR_ACTIVE,
+                Error::<T>::DominatorInactive
+            );
+            let to_dominator =
+                Dominators::<T>::try_get(&to).map_err(|_| Error::<T>::DominatorNotFound)?;
+            ensure!(
+                to_dominator.status == DOMINATOR_ACTIVE,
+                Error::<T>::DominatorInactive
+            );
+            let current_block = frame_system::Pallet::<T>::block_number();
+            LastRedelegations::<T>::try_mutate(&staker, |last| -> DispatchResult {
+                if let Some(last) = last {
+                    ensure!(
+                        current_block >= *last + T::RedelegateCooldown::get(),
+                        Error::<T>::RedelegateTooFrequent
+                    );
+                }
+                Ok(*last = Some(current_block))
+            })?;
+            Self::release_staking(&staker, &from, amount)?;
+            Self::stake_on(&staker, &to, amount)?;
+            Self::deposit_event(Event::TaoRedelegated(staker, from, to, amount));
+            Ok(().into())
+        }
+
+        /// stake the pending unstakings to `dominator` again, the latest unbonding chunks first
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::rebond())]
+        pub fn rebond(
+            origin: OriginFor<T>,
+            dominator: <T::Lookup as StaticLookup>::Source,
+            amount: Balance<T>,
+        ) -> DispatchResultWithPostInfo {
+            let staker = ensure_signed(origin)?;
+            let dominator = T::Lookup::lookup(dominator)?;
+            let mut remain = amount;
+            for (unlock_at, unbonding) in
+                Self::unbonding_chunks_of(staker.clone()).into_iter().rev()
+            {
+                if remain.is_zero() {
+                    break;
+                }
+                let rebonded = if unbonding > remain {
+                    remain
+                } else {
+                    unbonding
+                };
+                Unbondings::<T>::mutate_exists(&staker, &unlock_at, |v| {
+                    *v = Some(unbonding - rebonded).filter(|v| !v.is_zero())
+                });
+                PendingUnstakings::<T>::mutate_exists(&unlock_at, &staker, |v| {
+                    *v = Some(unbonding - rebonded).filter(|v| !v.is_zero())
+                });
+                remain -= rebonded;
+            }
+            ensure!(remain.is_zero(), Error::<T>::InsufficientUnbonding);
+            Self::unreserve(
+                RESERVE_FOR_PENDING_UNSTAKE,
+                staker.clone(),
+                T::Asset::native_token_id(),
+                amount,
+                &Self::system_account(),
+            )?;
+            Self::stake_on(&staker, &dominator, amount)?;
+            Self::deposit_event(Event::TaoRebonded(staker, dominator, amount));
+            Ok(().into())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::authorize())]
+        pub fn authorize(
+            origin: OriginFor<T>,
+            dominator: <T::Lookup as StaticLookup>::Source,
+            token_id: TokenId<T>,
+            amount: Balance<T>,
         ) -> DispatchResultWithPostInfo {
             let fund_owner = ensure_signed(origin)?;
             let dex = T::Lookup::lookup(dominator)?;
@@ -855,6 +1594,73 @@ pub mod pallet {
             Ok(().into())
         }
 
+        /// revoke from a dominator and authorize the funds to another one once the `TransferOut`
+        /// is proven, the funds are kept reserved during the migration
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::migrate_authorization())]
+        pub fn migrate_authorization(
+            origin: OriginFor<T>,
+            from: <T::Lookup as StaticLookup>::Source,
+            to: <T::Lookup as StaticLookup>::Source,
+            token_id: TokenId<T>,
+            amount: Balance<T>,
+        ) -> DispatchResultWithPostInfo {
+            let fund_owner = ensure_signed(origin)?;
+            let from = T::Lookup::lookup(from)?;
+            let to = T::Lookup::lookup(to)?;
+            Self::migrate_from(fund_owner, from, to, token_id, amount)?;
+            Ok(().into())
+        }
+
+        /// reclaim the stash of an `Authorize` receipt which the dominator didn't process in time
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::reclaim_authorizing())]
+        pub fn reclaim_authorizing(
+            origin: OriginFor<T>,
+            dominator: <T::Lookup as StaticLookup>::Source,
+        ) -> DispatchResultWithPostInfo {
+            let fund_owner = ensure_signed(origin)?;
+            let dex = T::Lookup::lookup(dominator)?;
+            Self::refund_expired_authorizing(fund_owner, dex)?;
+            Ok(().into())
+        }
+
+        /// dispatch a callback which failed after `TransferOut` again
+        #[transactional]
+        #[pallet::weight(<T as Config>::WeightInfo::retry_callback().saturating_add(*max_weight))]
+        pub fn retry_callback(
+            origin: OriginFor<T>,
+            id: u32,
+            max_weight: Weight,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let failed =
+                FailedCallbacks::<T>::get(&who, id).ok_or(Error::<T>::CallbackNotExists)?;
+            ensure!(
+                failed.callback.get_dispatch_info().weight <= max_weight,
+                Error::<T>::CallbackWeightTooLow
+            );
+            failed
+                .callback
+                .dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+                .map_err(|e| e.error)?;
+            FailedCallbacks::<T>::remove(&who, id);
+            Self::deposit_event(Event::CallbackRetried(who, id));
+            Ok(().into())
+        }
+
+        #[pallet::weight(<T as Config>::WeightInfo::discard_callback())]
+        pub fn discard_callback(origin: OriginFor<T>, id: u32) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(
+                FailedCallbacks::<T>::contains_key(&who, id),
+                Error::<T>::CallbackNotExists
+            );
+            FailedCallbacks::<T>::remove(&who, id);
+            Self::deposit_event(Event::CallbackDiscarded(who, id));
+            Ok(().into())
+        }
+
         #[transactional]
         #[pallet::weight(100_000)]
         pub fn listing(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
@@ -874,6 +1680,8 @@ pub mod pallet {
         pub who: AccountId,
         pub matched_volume: Balance,
         pub matched_amount: Balance,
+        pub base_origin: Balance,
+        pub quote_origin: Balance,
         pub base_value: Balance,
         pub quote_value: Balance,
     }
@@ -929,6 +1737,10 @@ pub mod pallet {
                 dominator.status == DOMINATOR_ACTIVE,
                 Error::<T>::DominatorInactive
             );
+            ensure!(
+                !Self::is_paused(&dex, PAUSE_AUTHORIZE),
+                Error::<T>::CommandPaused
+            );
             ensure!(
                 !Receipts::<T>::contains_key(&dex, &fund_owner),
                 Error::<T>::ReceiptAlreadyExists,
@@ -979,6 +1791,9 @@ pub mod pallet {
                     |ov| -> DispatchResult {
                         let av: Balance<T> = ov.take().unwrap_or(0.into());
                         if av > 0.into() {
+                            AuthorizedTotals::<T>::mutate(&dominator_id, token_id, |t| {
+                                *t = t.saturating_add(av)
+                            });
                             return Reserves::<T>::try_mutate(
                                 &(RESERVE_FOR_AUTHORIZING, fund_owner.clone(), token_id),
                                 &dominator_id,
@@ -998,6 +1813,7 @@ pub mod pallet {
                     &dominator_id,
                 )?;
                 Receipts::<T>::remove(dominator_id.clone(), &fund_owner);
+                PendingMigrations::<T>::remove(&dominator_id, &fund_owner);
             } else {
                 ensure!(
                     !Receipts::<T>::contains_key(&dominator_id, &fund_owner),
@@ -1020,11 +1836,183 @@ pub mod pallet {
             Ok(())
         }
 
+        #[transactional]
+        pub fn migrate_from(
+            fund_owner: T::AccountId,
+            from: T::AccountId,
+            to: T::AccountId,
+            token_id: TokenId<T>,
+            amount: Balance<T>,
+        ) -> DispatchResult {
+            ensure!(from != to, Error::<T>::InvalidMigration);
+            let dest = Dominators::<T>::try_get(&to).map_err(|_| Error::<T>::DominatorNotFound)?;
+            ensure!(
+                dest.status == DOMINATOR_ACTIVE,
+                Error::<T>::DominatorInactive
+            );
+            ensure!(
+                !Self::is_paused(&to, PAUSE_AUTHORIZE),
+                Error::<T>::CommandPaused
+            );
+            ensure!(
+                !Receipts::<T>::contains_key(&to, &fund_owner),
+                Error::<T>::ReceiptAlreadyExists,
+            );
+            Self::revoke_from(fund_owner.clone(), from.clone(), token_id, amount, None)?;
+            if Receipts::<T>::contains_key(&from, &fund_owner) {
+                PendingMigrations::<T>::insert(&from, &fund_owner, to);
+            } else {
+                // the evicted dominator has released the funds immediately
+                Self::authorize_to(fund_owner, to, token_id, amount)?;
+            }
+            Ok(())
+        }
+
+        /// move the proven `TransferOut` of a migration to the stash of the destination,
+        /// or release it if the destination couldn't accept it anymore
+        #[transactional]
+        fn complete_migration(
+            fund_owner: &T::AccountId,
+            from: &T::AccountId,
+            to: &T::AccountId,
+            token_id: TokenId<T>,
+            amount: Balance<T>,
+        ) -> DispatchResult {
+            let acceptable = Dominators::<T>::get(to)
+                .map(|d| d.status == DOMINATOR_ACTIVE)
+                .unwrap_or(false)
+                && !Self::is_paused(to, PAUSE_AUTHORIZE)
+                && !Receipts::<T>::contains_key(to, fund_owner);
+            if !acceptable {
+                Self::unreserve(
+                    RESERVE_FOR_AUTHORIZING,
+                    fund_owner.clone(),
+                    token_id,
+                    amount,
+                    from,
+                )?;
+                Self::deposit_event(Event::AuthorizationMigrationAborted(
+                    fund_owner.clone(),
+                    from.clone(),
+                    to.clone(),
+                    token_id,
+                    amount,
+                ));
+                return Ok(());
+            }
+            Reserves::<T>::try_mutate_exists(
+                &(RESERVE_FOR_AUTHORIZING, fund_owner.clone(), token_id),
+                from,
+                |ov| -> DispatchResult {
+                    let reserve = ov
+                        .take()
+                        .ok_or(Error::<T>::InsufficientBalance)?
+                        .checked_sub(&amount)
+                        .ok_or(Error::<T>::InsufficientBalance)?;
+                    if reserve > Zero::zero() {
+                        ov.replace(reserve);
+                    }
+                    Ok(())
+                },
+            )?;
+            AuthorizedTotals::<T>::mutate(from, token_id, |t| *t = t.saturating_sub(amount));
+            Reserves::<T>::try_mutate(
+                &(RESERVE_FOR_AUTHORIZING_STASH, fund_owner.clone(), token_id),
+                to,
+                |ov| -> DispatchResult {
+                    Ok(*ov = ov.checked_add(&amount).ok_or(Error::<T>::Overflow)?)
+                },
+            )?;
+            let block_number = frame_system::Pallet::<T>::block_number();
+            Receipts::<T>::insert(
+                to,
+                fund_owner,
+                Receipt::Authorize(token_id, amount, block_number),
+            );
+            Self::deposit_event(Event::AuthorizationMigrated(
+                fund_owner.clone(),
+                from.clone(),
+                to.clone(),
+                token_id,
+                amount,
+            ));
+            Ok(())
+        }
+
+        #[transactional]
+        pub fn refund_expired_authorizing(
+            fund_owner: T::AccountId,
+            dex: T::AccountId,
+        ) -> DispatchResult {
+            let r = Receipts::<T>::get(&dex, &fund_owner).ok_or(Error::<T>::ReceiptNotExists)?;
+            let (token_id, amount, authorize_at) = match r {
+                Receipt::Authorize(token_id, amount, authorize_at) => {
+                    (token_id, amount, authorize_at)
+                }
+                _ => return Err(Error::<T>::ReceiptNotExists.into()),
+            };
+            let current_block = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                Self::is_authorizing_expired(authorize_at, current_block),
+                Error::<T>::AuthorizingNotExpired
+            );
+            Self::unreserve(
+                RESERVE_FOR_AUTHORIZING_STASH,
+                fund_owner.clone(),
+                token_id,
+                amount,
+                &dex,
+            )?;
+            Receipts::<T>::remove(&dex, &fund_owner);
+            Self::deposit_event(Event::AuthorizingRefunded(
+                fund_owner, dex, token_id, amount,
+            ));
+            Ok(())
+        }
+
+        pub fn is_authorizing_expired(authorize_at: T::BlockNumber, now: T::BlockNumber) -> bool {
+            now >= authorize_at + T::AuthorizingExpiry::get()
+        }
+
+        /// the failed callbacks are saved and could be retried by the fund owner later
+        fn dispatch_callback(
+            who: &T::AccountId,
+            callback: T::Callback,
+            now: T::BlockNumber,
+        ) -> DispatchResult {
+            if let Err(e) = callback
+                .clone()
+                .dispatch(frame_system::RawOrigin::Signed(who.clone()).into())
+                .map_err(|e| e.error)
+            {
+                log::error!("execute callback of {:?} failed: {:?}", who, e);
+                let id =
+                    NextFailedCallbackId::<T>::try_mutate(|id| -> Result<u32, DispatchError> {
+                        let current = *id;
+                        *id = id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+                        Ok(current)
+                    })?;
+                FailedCallbacks::<T>::insert(
+                    who,
+                    id,
+                    FailedCallback {
+                        callback,
+                        error: e,
+                        failed_at: now,
+                    },
+                );
+                Self::deposit_event(Event::CallbackFailed(who.clone(), id, e));
+            }
+            Ok(())
+        }
+
         fn verify_batch(
             dominator_id: T::AccountId,
             dominator: &Dominator<Balance<T>, BlockNumberFor<T>>,
             proofs: Vec<Proof<T::AccountId>>,
         ) -> DispatchResultWithPostInfo {
+            let count = proofs.len() as u32;
+            ensure!(count <= MAX_PROOFS_PER_BATCH, Error::<T>::ProofTooLarge);
             let mut known_root = dominator.merkle_root;
             let mut incr: BTreeMap<TokenId<T>, (Balance<T>, Balance<T>)> = BTreeMap::new();
             for proof in proofs.into_iter() {
@@ -1048,7 +2036,7 @@ pub mod pallet {
             for (token_id, trade) in incr.into_iter() {
                 T::Indicator::set_price(token_id, trade.0, trade.1, current_block);
             }
-            Ok(().into())
+            Ok(Some(<T as Config>::WeightInfo::verify(count)).into())
         }
 
         #[transactional]
@@ -1102,7 +2090,7 @@ pub mod pallet {
                         quote.into(),
                     );
                     ensure!(
-                        T::Asset::is_stable(&quote.into()),
+                        Self::is_quote_supported(base.into(), quote.into()),
                         Error::<T>::UnsupportedQuoteCurrency
                     );
                     let cr = Self::verify_ask_limit(
@@ -1119,22 +2107,34 @@ pub mod pallet {
                     )?;
                     if cr.users_mutation.len() > 1 {
                         for d in cr.users_mutation.iter() {
-                            Self::clear(&d.who, dominator_id, base.into(), d.base_value)?;
-                            Self::clear(&d.who, dominator_id, quote.into(), d.quote_value)?;
-                            T::Rewarding::save_trading(&d.who, d.matched_volume, current_block)?;
+                            Self::clear(
+                                &d.who,
+                                dominator_id,
+                                base.into(),
+                                d.base_origin,
+                                d.base_value,
+                            )?;
+                            Self::clear(
+                                &d.who,
+                                dominator_id,
+                                quote.into(),
+                                d.quote_origin,
+                                d.quote_value,
+                            )?;
+                            T::Rewarding::save_trading(
+                                &d.who,
+                                Self::to_stable_volume(quote.into(), d.matched_volume),
+                                current_block,
+                            )?;
                         }
                         if let Some(t) = cr.users_mutation.last() {
                             trade.token_id = base.into();
                             trade.amount += t.matched_amount;
-                            trade.vol += t.matched_volume;
+                            trade.vol += Self::to_stable_volume(quote.into(), t.matched_volume);
                         }
                     }
-                    Self::put_profit(dominator_id, current_season, quote.into(), cr.quote_fee)?;
-                    if cr.base_fee != Zero::zero() {
-                        T::Asset::try_mutate_account(&base.into(), dominator_id, |b| {
-                            Ok(b.0 += cr.base_fee)
-                        })?;
-                    }
+                    Self::share_fee(dominator_id, current_season, base.into(), cr.base_fee)?;
+                    Self::share_fee(dominator_id, current_season, quote.into(), cr.quote_fee)?;
                 }
                 Command::BidLimit(price, amount, maker_fee, taker_fee, base, quote) => {
                     Self::check_fee(taker_fee.into(), maker_fee.into())?;
@@ -1154,7 +2154,7 @@ pub mod pallet {
                         quote.into(),
                     );
                     ensure!(
-                        T::Asset::is_stable(&quote.into()),
+                        Self::is_quote_supported(base.into(), quote.into()),
                         Error::<T>::UnsupportedQuoteCurrency
                     );
                     let cr = Self::verify_bid_limit(
@@ -1171,23 +2171,35 @@ pub mod pallet {
                     )?;
                     if cr.users_mutation.len() > 1 {
                         for d in cr.users_mutation.iter() {
-                            Self::clear(&d.who, dominator_id, base.into(), d.base_value)?;
-                            Self::clear(&d.who, dominator_id, quote.into(), d.quote_value)?;
-                            T::Rewarding::save_trading(&d.who, d.matched_volume, current_block)?;
+                            Self::clear(
+                                &d.who,
+                                dominator_id,
+                                base.into(),
+                                d.base_origin,
+                                d.base_value,
+                            )?;
+                            Self::clear(
+                                &d.who,
+                                dominator_id,
+                                quote.into(),
+                                d.quote_origin,
+                                d.quote_value,
+                            )?;
+                            T::Rewarding::save_trading(
+                                &d.who,
+                                Self::to_stable_volume(quote.into(), d.matched_volume),
+                                current_block,
+                            )?;
                             trade.token_id = base.into();
                         }
                         if let Some(t) = cr.users_mutation.last() {
                             trade.token_id = base.into();
                             trade.amount += t.matched_amount;
-                            trade.vol += t.matched_volume;
+                            trade.vol += Self::to_stable_volume(quote.into(), t.matched_volume);
                         }
                     }
-                    Self::put_profit(dominator_id, current_season, quote.into(), cr.quote_fee)?;
-                    if cr.base_fee != Zero::zero() {
-                        T::Asset::try_mutate_account(&base.into(), dominator_id, |b| {
-                            Ok(b.0 += cr.base_fee)
-                        })?;
-                    }
+                    Self::share_fee(dominator_id, current_season, base.into(), cr.base_fee)?;
+                    Self::share_fee(dominator_id, current_season, quote.into(), cr.quote_fee)?;
                 }
                 Command::Cancel(base, quote) => {
                     let (base, quote): (u32, u32) = (base.into(), quote.into());
@@ -1208,28 +2220,26 @@ pub mod pallet {
                     };
                     ensure!(exists, Error::<T>::ReceiptNotExists);
                     Self::verify_transfer_out(currency, amount, &proof.user_id, &proof.leaves)?;
-                    Self::unreserve(
-                        RESERVE_FOR_AUTHORIZING,
-                        proof.user_id.clone(),
-                        currency.into(),
-                        amount.into(),
-                        &dominator_id,
-                    )?;
                     Receipts::<T>::remove(dominator_id, &proof.user_id);
+                    match PendingMigrations::<T>::take(dominator_id, &proof.user_id) {
+                        Some(to) => Self::complete_migration(
+                            &proof.user_id,
+                            dominator_id,
+                            &to,
+                            currency.into(),
+                            amount.into(),
+                        )?,
+                        None => Self::unreserve(
+                            RESERVE_FOR_AUTHORIZING,
+                            proof.user_id.clone(),
+                            currency.into(),
+                            amount.into(),
+                            &dominator_id,
+                        )?,
+                    }
                     match r {
                         Receipt::RevokeWithCallback(_, _, _, cb) => {
-                            if let Err(e) = cb
-                                .dispatch(
-                                    frame_system::RawOrigin::Signed(proof.user_id.clone()).into(),
-                                )
-                                .map_err(|e| e.error)
-                            {
-                                log::error!(
-                                    "execute callback of {:?} failed: {:?}",
-                                    proof.user_id,
-                                    e
-                                );
-                            }
+                            Self::dispatch_callback(&proof.user_id, cb, current_block)?;
                         }
                         _ => {}
                     }
@@ -1238,13 +2248,16 @@ pub mod pallet {
                     let (currency, amount) = (currency.into(), amount.into());
                     let r = Receipts::<T>::get(dominator_id, &proof.user_id)
                         .ok_or(Error::<T>::ReceiptNotExists)?;
-                    let exists = match r {
-                        Receipt::Authorize(id, value, _) => {
-                            id.into() == currency && value.into() == amount
-                        }
-                        _ => false,
+                    let (exists, expired) = match r {
+                        Receipt::Authorize(id, value, authorize_at) => (
+                            id.into() == currency && value.into() == amount,
+                            Self::is_authorizing_expired(authorize_at, current_block),
+                        ),
+                        _ => (false, false),
                     };
                     ensure!(exists, Error::<T>::ReceiptNotExists);
+                    // the expired receipt must be answered by `RejectTransferIn`
+                    ensure!(!expired, Error::<T>::AuthorizingExpired);
                     Self::verify_transfer_in(currency, amount, &proof.user_id, &proof.leaves)?;
                     //stash->authorizing
                     Reserves::<T>::remove(
@@ -1266,6 +2279,9 @@ pub mod pallet {
                             Ok(*ov = ov.checked_add(&amount.into()).ok_or(Error::<T>::Overflow)?)
                         },
                     )?;
+                    AuthorizedTotals::<T>::mutate(dominator_id, currency.into(), |t| {
+                        *t = t.saturating_add(amount.into())
+                    });
                     Receipts::<T>::remove(dominator_id, &proof.user_id);
                 }
                 Command::RejectTransferOut(currency, amount) => {
@@ -1289,6 +2305,7 @@ pub mod pallet {
                         &proof.leaves,
                     )?;
                     Receipts::<T>::remove(&dominator_id, &proof.user_id);
+                    PendingMigrations::<T>::remove(&dominator_id, &proof.user_id);
                     // needn't step forward
                     return Ok(trade);
                 }
@@ -1298,19 +2315,35 @@ pub mod pallet {
                         return Ok(trade);
                     }
                     let r = r.unwrap();
-                    ensure!(
-                        matches!(r, Receipt::Authorize(_, _, _)),
-                        Error::<T>::ReceiptNotExists
-                    );
-                    Receipts::<T>::remove(&dominator_id, &proof.user_id);
-                    return Ok(trade);
-                }
-            }
-            Dominators::<T>::mutate(&dominator_id, |d| {
-                let update = d.as_mut().unwrap();
+                    let (currency, amount, authorize_at) = match r {
+                        Receipt::Authorize(currency, amount, authorize_at) => {
+                            (currency, amount, authorize_at)
+                        }
+                        _ => return Err(Error::<T>::ReceiptNotExists.into()),
+                    };
+                    if Self::is_authorizing_expired(authorize_at, current_block) {
+                        Self::refund_expired_authorizing(
+                            proof.user_id.clone(),
+                            dominator_id.clone(),
+                        )?;
+                        Self::deposit_event(Event::TransferInExpired(
+                            dominator_id.clone(),
+                            proof.user_id.clone(),
+                            currency,
+                            amount,
+                        ));
+                    } else {
+                        Receipts::<T>::remove(&dominator_id, &proof.user_id);
+                    }
+                    return Ok(trade);
+                }
+            }
+            Dominators::<T>::mutate(&dominator_id, |d| {
+                let update = d.as_mut().unwrap();
                 update.merkle_root = proof.root;
                 update.sequence = (proof.event_id, current_block);
             });
+            Self::checkpoint_root(dominator_id, proof.event_id, current_block, proof.root);
             trade.root = proof.root;
             Ok(trade)
         }
@@ -1424,6 +2457,8 @@ pub mod pallet {
                     who: maker_q_id,
                     matched_volume: quote_decr.into(),
                     matched_amount: base_incr.into(),
+                    base_origin: mb0.into(),
+                    quote_origin: mq0.into(),
                     base_value: mb1.into(),
                     quote_value: mq1.into(),
                 });
@@ -1447,6 +2482,8 @@ pub mod pallet {
                 who: taker_b_id,
                 matched_volume: mq_delta.into(),
                 matched_amount: tb_delta.into(),
+                base_origin: (tba0 + tbf0).into(),
+                quote_origin: (tqa0 + tqf0).into(),
                 base_value: (tba1 + tbf1).into(),
                 quote_value: (tqa1 + tqf1).into(),
             });
@@ -1636,6 +2673,8 @@ pub mod pallet {
                     who: maker_b_id,
                     matched_volume: quote_incr.into(),
                     matched_amount: base_decr.into(),
+                    base_origin: mb0.into(),
+                    quote_origin: mq0.into(),
                     base_value: mb1.into(),
                     quote_value: mq1.into(),
                 });
@@ -1669,6 +2708,8 @@ pub mod pallet {
                 who: taker_b_id,
                 matched_volume: tq_delta.into(),
                 matched_amount: mb_delta.into(),
+                base_origin: (tba0 + tbf0).into(),
+                quote_origin: (tqa0 + tqf0).into(),
                 base_value: (tba1.checked_add(tbf1).ok_or(Error::<T>::Overflow)?).into(),
                 quote_value: (tqa1.checked_add(tqf1).ok_or(Error::<T>::Overflow)?).into(),
             });
@@ -1881,7 +2922,7 @@ pub mod pallet {
             Reserves::<T>::get(&(RESERVE_FOR_AUTHORIZING, who, token_id), dominator) >= amount
         }
 
-        fn has_authorized_exactly_on(
+        pub(crate) fn has_authorized_exactly_on(
             who: T::AccountId,
             token_id: TokenId<T>,
             amount: Balance<T>,
@@ -1889,19 +2930,22 @@ pub mod pallet {
         ) -> bool {
             let confirmed =
                 Reserves::<T>::get(&(RESERVE_FOR_AUTHORIZING, who, token_id), dominator);
-            // FIXME the offchain matchers loose the precesions
+            // the offchain matchers round the balances to the decimals of token
+            let tolerance =
+                T::Asset::precision_tolerance(&token_id).max(MIN_CLEARING_TOLERANCE.into());
             if confirmed >= amount {
-                confirmed - amount <= 100000000000u128.into()
+                confirmed - amount <= tolerance
             } else {
-                amount - confirmed <= 100000000000u128.into()
+                amount - confirmed <= tolerance
             }
         }
 
         #[transactional]
-        fn clear(
+        pub(crate) fn clear(
             who: &T::AccountId,
             dominator: &T::AccountId,
             token_id: TokenId<T>,
+            origin: Balance<T>,
             balance: Balance<T>,
         ) -> DispatchResult {
             Reserves::<T>::try_mutate(
@@ -1909,51 +2953,133 @@ pub mod pallet {
                 dominator,
                 |reserved| -> DispatchResult {
                     T::Asset::try_mutate_account(&token_id, who, |b| -> DispatchResult {
-                        // FIXME the offchain matchers loose the precesions
                         b.1 =
                             b.1.checked_sub(reserved)
-                                .unwrap_or_default()
+                                .ok_or(Error::<T>::InsufficientBalance)?
                                 .checked_add(&balance)
                                 .ok_or(Error::<T>::Overflow)?;
                         Ok(())
                     })?;
+                    // the difference between the confirmed and the proven is the rounding dust
+                    if *reserved != origin {
+                        Dusts::<T>::mutate(dominator, token_id, |dust| {
+                            if *reserved > origin {
+                                dust.surplus = dust.surplus.saturating_add(*reserved - origin);
+                            } else {
+                                dust.deficit = dust.deficit.saturating_add(origin - *reserved);
+                            }
+                        });
+                    }
+                    AuthorizedTotals::<T>::mutate(dominator, token_id, |t| {
+                        *t = t.saturating_sub(*reserved).saturating_add(balance)
+                    });
                     *reserved = balance;
                     Ok(())
                 },
             )
         }
 
-        /// take shares from dominator, return season should update
+        fn claim_shares_of(
+            signer: &T::AccountId,
+            dex: &T::AccountId,
+            dominator: &Dominator<Balance<T>, T::BlockNumber>,
+        ) -> DispatchResult {
+            let staking =
+                Stakings::<T>::try_get(dex, signer).map_err(|_| Error::<T>::InvalidStaking)?;
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let current_season = Self::current_season(current_block, dominator.start_from);
+            Self::checkpoint_staking(dex, dominator.start_from, dominator.staked, current_block);
+            let distribution = Distribution {
+                from_season: staking.from_season,
+                to_season: current_season,
+                staking: staking.amount,
+                weighted: staking.weighted,
+                compound: dominator.status == DOMINATOR_ACTIVE
+                    || dominator.status == DOMINATOR_INACTIVE,
+            };
+            let compounded =
+                Stakings::<T>::try_mutate(dex, signer, |s| -> Result<Balance<T>, DispatchError> {
+                    let compounded = Self::take_shares(signer, dex, &distribution)?;
+                    let amount = s.amount + compounded;
+                    Self::update_weighted(
+                        dex,
+                        s,
+                        dominator.start_from,
+                        current_season,
+                        current_block,
+                        amount,
+                    );
+                    Ok(compounded)
+                })?;
+            if !compounded.is_zero() {
+                Dominators::<T>::mutate(dex, |d| {
+                    if let Some(d) = d {
+                        d.staked += compounded;
+                        if d.status == DOMINATOR_INACTIVE
+                            && d.staked >= T::DominatorOnlineThreshold::get()
+                        {
+                            d.status = DOMINATOR_ACTIVE;
+                            Self::deposit_event(Event::DominatorOnline(dex.clone()));
+                        }
+                    }
+                });
+            }
+            Ok(())
+        }
+
+        /// take shares from dominator, the seasons before `to_season` must be finalized,
+        /// returns the native shares restaked to the `dominator`
         #[transactional]
         fn take_shares(
             staker: &T::AccountId,
             dominator: &T::AccountId,
             distributions: &Distribution<T>,
-        ) -> Result<Season, DispatchError> {
+        ) -> Result<Balance<T>, DispatchError> {
             if distributions.to_season == distributions.from_season {
-                return Ok(distributions.from_season);
+                return Ok(Zero::zero());
             }
-            let mut shares: BTreeMap<TokenId<T>, u128> = BTreeMap::new();
-            for season in distributions.from_season..distributions.to_season {
-                let bonus = Bonuses::<T>::get(dominator, season);
-                if bonus.staked.is_zero() || bonus.profit.is_empty() {
-                    continue;
-                }
-                // TODO associated type
-                let staking: u128 = distributions.staking.into();
-                let total_staking: u128 = bonus.staked.into();
-                let r: Perquintill = Perquintill::from_rational(staking, total_staking);
-                for (token_id, profit) in bonus.profit.into_iter() {
-                    shares
-                        .entry(token_id)
-                        .and_modify(|share| *share += r * profit.into())
-                        .or_insert(r * profit.into());
+            let from =
+                SeasonRewards::<T>::get(dominator, distributions.from_season).unwrap_or_default();
+            let shares = Self::shares_of(
+                distributions.staking,
+                distributions.weighted,
+                &from,
+                &AccumulatedRewards::<T>::get(dominator),
+            );
+            let payee = if distributions.compound {
+                AutoCompounds::<T>::get(staker)
+            } else {
+                None
+            };
+            let beneficiary = Payees::<T>::get(staker).unwrap_or_else(|| staker.clone());
+            let native = T::Asset::native_token_id();
+            let mut compounded: Balance<T> = Zero::zero();
+            for (token_id, profit) in shares {
+                let to = match payee {
+                    Some(ref payee) if token_id != native => payee,
+                    Some(_) => staker,
+                    None => &beneficiary,
+                };
+                T::Asset::try_mutate_account(&token_id, to, |b| Ok(b.0 += profit.into()))?;
+                if payee.is_some() && token_id == native {
+                    compounded = profit.into();
                 }
             }
-            for (token_id, profit) in shares {
-                T::Asset::try_mutate_account(&token_id, staker, |b| Ok(b.0 += profit.into()))?;
+            if !compounded.is_zero() {
+                Self::reserve(
+                    RESERVE_FOR_STAKING,
+                    staker.clone(),
+                    native,
+                    compounded,
+                    dominator,
+                )?;
+                Self::deposit_event(Event::SharesCompounded(
+                    staker.clone(),
+                    dominator.clone(),
+                    compounded,
+                ));
             }
-            Ok(distributions.to_season)
+            Ok(compounded)
         }
 
         #[transactional]
@@ -1974,7 +3100,11 @@ pub mod pallet {
                     T::Asset::reserve(&token, &fund_owner, value)?;
                     Ok(*ov = ov.checked_add(&value).ok_or(Error::<T>::Overflow)?)
                 },
-            )
+            )?;
+            if reserve_id == RESERVE_FOR_AUTHORIZING {
+                AuthorizedTotals::<T>::mutate(to, token, |t| *t = t.saturating_add(value));
+            }
+            Ok(())
         }
 
         #[transactional]
@@ -2002,7 +3132,11 @@ pub mod pallet {
                     }
                     Ok(())
                 },
-            )
+            )?;
+            if reserve_id == RESERVE_FOR_AUTHORIZING {
+                AuthorizedTotals::<T>::mutate(from, token, |t| *t = t.saturating_sub(value));
+            }
+            Ok(())
         }
 
         #[transactional]
@@ -2022,30 +3156,52 @@ pub mod pallet {
                     dominator.status == DOMINATOR_ACTIVE || dominator.status == DOMINATOR_INACTIVE,
                     Error::<T>::DominatorStatusInvalid
                 );
-                Stakings::<T>::try_mutate(&dominator_id, &staker, |staking| -> DispatchResult {
-                    Self::reserve(
-                        RESERVE_FOR_STAKING,
-                        staker.clone(),
-                        T::Asset::native_token_id(),
-                        amount,
-                        &dominator_id,
-                    )?;
-                    let current_block = frame_system::Pallet::<T>::block_number();
-                    let current_season = Self::current_season(current_block, dominator.start_from);
-                    if !staking.amount.is_zero() {
-                        //not first staking
-                        let distribution = Distribution {
-                            from_season: staking.from_season,
-                            to_season: current_season,
-                            staking: staking.amount,
-                        };
-                        Self::take_shares(staker, dominator_id, &distribution)?;
-                    }
-                    staking.amount += amount;
-                    staking.from_season = current_season;
-                    Ok(())
-                })?;
-                dominator.staked += amount;
+                let current_block = frame_system::Pallet::<T>::block_number();
+                Self::checkpoint_staking(
+                    dominator_id,
+                    dominator.start_from,
+                    dominator.staked,
+                    current_block,
+                );
+                let compounded = Stakings::<T>::try_mutate(
+                    &dominator_id,
+                    &staker,
+                    |staking| -> Result<Balance<T>, DispatchError> {
+                        Self::reserve(
+                            RESERVE_FOR_STAKING,
+                            staker.clone(),
+                            T::Asset::native_token_id(),
+                            amount,
+                            &dominator_id,
+                        )?;
+                        let current_season =
+                            Self::current_season(current_block, dominator.start_from);
+                        let mut compounded: Balance<T> = Zero::zero();
+                        if !staking.amount.is_zero() {
+                            //not first staking
+                            let distribution = Distribution {
+                                from_season: staking.from_season,
+                                to_season: current_season,
+                                staking: staking.amount,
+                                weighted: staking.weighted,
+                                compound: true,
+                            };
+                            compounded = Self::take_shares(staker, dominator_id, &distribution)?;
+                        }
+                        let new_amount = staking.amount + amount + compounded;
+                        Self::update_weighted(
+                            dominator_id,
+                            staking,
+                            dominator.start_from,
+                            current_season,
+                            current_block,
+                            new_amount,
+                        );
+                        Ok(compounded)
+                    },
+                )?;
+                StakedDominators::<T>::insert(staker, dominator_id, ());
+                dominator.staked += amount + compounded;
                 let dominator_old_status = dominator.status;
                 dominator.status = if dominator.staked >= T::DominatorOnlineThreshold::get() {
                     DOMINATOR_ACTIVE
@@ -2072,6 +3228,38 @@ pub mod pallet {
             staker: &T::AccountId,
             dominator_id: &T::AccountId,
             amount: Balance<T>,
+        ) -> DispatchResult {
+            Self::release_staking(staker, dominator_id, amount)?;
+            Self::reserve(
+                RESERVE_FOR_PENDING_UNSTAKE,
+                staker.clone(),
+                T::Asset::native_token_id(),
+                amount,
+                &Self::system_account(),
+            )?;
+            let current_block = frame_system::Pallet::<T>::block_number();
+            let unlock_at = current_block - current_block % T::DominatorCheckGracePeriod::get();
+            let unlock_at = unlock_at + T::UnbondingDelay::get();
+            PendingUnstakings::<T>::try_mutate(&unlock_at, &staker, |v| -> DispatchResult {
+                Ok(*v = v.checked_add(&amount).ok_or(Error::<T>::Overflow)?)
+            })?;
+            Unbondings::<T>::try_mutate(&staker, &unlock_at, |v| -> DispatchResult {
+                Ok(*v = v.checked_add(&amount).ok_or(Error::<T>::Overflow)?)
+            })?;
+            Self::deposit_event(Event::TaoUnstaked(
+                staker.clone(),
+                dominator_id.clone(),
+                amount,
+            ));
+            Ok(())
+        }
+
+        /// take `amount` out of the staking of a dominator and unreserve it to the staker immediately
+        #[transactional]
+        fn release_staking(
+            staker: &T::AccountId,
+            dominator_id: &T::AccountId,
+            amount: Balance<T>,
         ) -> DispatchResult {
             Dominators::<T>::try_mutate_exists(dominator_id, |exists| -> DispatchResult {
                 ensure!(exists.is_some(), Error::<T>::DominatorNotFound);
@@ -2084,58 +3272,62 @@ pub mod pallet {
                     .staked
                     .checked_sub(&amount)
                     .ok_or(Error::<T>::InsufficientBalance)?;
-                Stakings::<T>::try_mutate_exists(&dominator_id, &staker, |s| -> DispatchResult {
-                    let staking = s.take();
-                    ensure!(staking.is_some(), Error::<T>::InvalidStaking);
-                    let mut staking = staking.unwrap();
-                    let remain = staking
-                        .amount
-                        .checked_sub(&amount)
-                        .ok_or(Error::<T>::InsufficientBalance)?;
-                    ensure!(
-                        remain.is_zero() || remain >= T::MinimalStakingAmount::get(),
-                        Error::<T>::LittleStakingAmount
-                    );
-                    Self::unreserve(
-                        RESERVE_FOR_STAKING,
-                        staker.clone(),
-                        T::Asset::native_token_id(),
-                        amount,
-                        &dominator_id,
-                    )?;
-                    Self::reserve(
-                        RESERVE_FOR_PENDING_UNSTAKE,
-                        staker.clone(),
-                        T::Asset::native_token_id(),
-                        amount,
-                        &Self::system_account(),
-                    )?;
-                    let current_block = frame_system::Pallet::<T>::block_number();
-                    let current_season = Self::current_season(current_block, dominator.start_from);
-                    let unlock_at =
-                        current_block - current_block % T::DominatorCheckGracePeriod::get();
-                    let unlock_at = unlock_at + UNSTAKE_DELAY_BLOCKS.into();
-                    PendingUnstakings::<T>::try_mutate(
-                        &unlock_at,
-                        &staker,
-                        |v| -> DispatchResult {
-                            Ok(*v = v.checked_add(&amount).ok_or(Error::<T>::Overflow)?)
-                        },
-                    )?;
-                    let distribution = Distribution {
-                        from_season: staking.from_season,
-                        to_season: current_season,
-                        staking: staking.amount,
-                    };
-                    Self::take_shares(staker, dominator_id, &distribution)?;
-                    staking.from_season = current_season;
-                    staking.amount = remain;
-                    if !remain.is_zero() {
-                        s.replace(staking);
-                    }
-                    Ok(())
-                })?;
-                dominator.staked = dominator_total_staking;
+                let current_block = frame_system::Pallet::<T>::block_number();
+                Self::checkpoint_staking(
+                    dominator_id,
+                    dominator.start_from,
+                    dominator.staked,
+                    current_block,
+                );
+                let compounded = Stakings::<T>::try_mutate_exists(
+                    &dominator_id,
+                    &staker,
+                    |s| -> Result<Balance<T>, DispatchError> {
+                        let staking = s.take();
+                        ensure!(staking.is_some(), Error::<T>::InvalidStaking);
+                        let mut staking = staking.unwrap();
+                        let remain = staking
+                            .amount
+                            .checked_sub(&amount)
+                            .ok_or(Error::<T>::InsufficientBalance)?;
+                        ensure!(
+                            remain.is_zero() || remain >= T::MinimalStakingAmount::get(),
+                            Error::<T>::LittleStakingAmount
+                        );
+                        Self::unreserve(
+                            RESERVE_FOR_STAKING,
+                            staker.clone(),
+                            T::Asset::native_token_id(),
+                            amount,
+                            &dominator_id,
+                        )?;
+                        let current_season =
+                            Self::current_season(current_block, dominator.start_from);
+                        let distribution = Distribution {
+                            from_season: staking.from_season,
+                            to_season: current_season,
+                            staking: staking.amount,
+                            weighted: staking.weighted,
+                            compound: !remain.is_zero() && dominator.status != DOMINATOR_EVICTED,
+                        };
+                        let compounded = Self::take_shares(staker, dominator_id, &distribution)?;
+                        Self::update_weighted(
+                            dominator_id,
+                            &mut staking,
+                            dominator.start_from,
+                            current_season,
+                            current_block,
+                            remain + compounded,
+                        );
+                        if staking.amount.is_zero() {
+                            StakedDominators::<T>::remove(staker, dominator_id);
+                        } else {
+                            s.replace(staking);
+                        }
+                        Ok(compounded)
+                    },
+                )?;
+                dominator.staked = dominator_total_staking + compounded;
                 let dominator_old_status = dominator.status;
                 if dominator.status != DOMINATOR_EVICTED {
                     dominator.status = if dominator.staked >= T::DominatorOnlineThreshold::get() {
@@ -2144,11 +3336,6 @@ pub mod pallet {
                         DOMINATOR_INACTIVE
                     };
                 }
-                Self::deposit_event(Event::TaoUnstaked(
-                    staker.clone(),
-                    dominator_id.clone(),
-                    amount,
-                ));
                 if dominator.status == DOMINATOR_INACTIVE
                     && dominator_old_status == DOMINATOR_ACTIVE
                 {
@@ -2170,6 +3357,574 @@ pub mod pallet {
             claim_at + (season * T::SeasonDuration::get().into()).into()
         }
 
+        fn season_duration() -> u128 {
+            let duration: u32 = T::SeasonDuration::get().into();
+            duration.into()
+        }
+
+        /// stake × blocks of a staking in `season`, the `season` must not be earlier than `from_season`
+        fn weighted_of_season(
+            amount: Balance<T>,
+            weighted: u128,
+            from_season: Season,
+            season: Season,
+        ) -> u128 {
+            if season == from_season {
+                weighted
+            } else {
+                let amount: u128 = amount.into();
+                amount.saturating_mul(Self::season_duration())
+            }
+        }
+
+        /// settle the stake × blocks of a staking before changing its amount to `new_amount`,
+        /// the shares before `current_season` must be taken already
+        fn update_weighted(
+            dominator: &T::AccountId,
+            staking: &mut Staking<Balance<T>>,
+            claim_at: T::BlockNumber,
+            current_season: Season,
+            now: T::BlockNumber,
+            new_amount: Balance<T>,
+        ) {
+            if !staking.amount.is_zero() {
+                Self::release_season(dominator, staking.from_season, current_season);
+            }
+            if !new_amount.is_zero() {
+                StakingCounts::<T>::mutate(dominator, current_season, |c| *c += 1);
+            }
+            let season_end = Self::start_block_of_season(claim_at, current_season + 1);
+            let remain: u32 = (season_end - now).into();
+            let remain: u128 = remain.into();
+            let old_amount: u128 = staking.amount.into();
+            let projected = Self::weighted_of_season(
+                staking.amount,
+                staking.weighted,
+                staking.from_season,
+                current_season,
+            );
+            let new: u128 = new_amount.into();
+            staking.weighted = projected
+                .saturating_sub(old_amount.saturating_mul(remain))
+                .saturating_add(new.saturating_mul(remain));
+            staking.from_season = current_season;
+            staking.amount = new_amount;
+        }
+
+        /// stake × blocks of a dominator per season which are not accumulated into `Bonuses` yet
+        fn uncheckpointed_staking(
+            dominator: &T::AccountId,
+            claim_at: T::BlockNumber,
+            staked: Balance<T>,
+            now: T::BlockNumber,
+        ) -> Vec<(Season, u128)> {
+            let mut from = StakingCheckpoints::<T>::get(dominator).unwrap_or(claim_at);
+            let staked: u128 = staked.into();
+            let mut weighted = Vec::new();
+            while from < now {
+                let season = Self::current_season(from, claim_at);
+                let season_end = Self::start_block_of_season(claim_at, season + 1);
+                let to = if season_end < now { season_end } else { now };
+                let blocks: u32 = (to - from).into();
+                weighted.push((season, staked.saturating_mul(blocks.into())));
+                from = to;
+            }
+            weighted
+        }
+
+        /// accumulate the stake × blocks of a dominator into `Bonuses` until `now`,
+        /// must be called before the total staking of the dominator changes
+        fn checkpoint_staking(
+            dominator: &T::AccountId,
+            claim_at: T::BlockNumber,
+            staked: Balance<T>,
+            now: T::BlockNumber,
+        ) -> u64 {
+            let weighted = Self::uncheckpointed_staking(dominator, claim_at, staked, now);
+            if weighted.is_empty() {
+                return 0;
+            }
+            let mut writes = 1u64;
+            let current_season = Self::current_season(now, claim_at);
+            for (season, w) in weighted.into_iter() {
+                let ended = season < current_season;
+                if w != 0 || (ended && !staked.is_zero()) {
+                    Bonuses::<T>::mutate(dominator, season, |b| {
+                        b.weighted = b.weighted.saturating_add(w);
+                        if ended {
+                            b.staked = staked;
+                        }
+                    });
+                    writes += 1;
+                }
+                if ended {
+                    Self::finalize_season(dominator, season);
+                    writes += 2;
+                }
+            }
+            StakingCheckpoints::<T>::insert(dominator, now);
+            writes
+        }
+
+        fn per_stake_of(bonus: &Bonus<TokenId<T>, Balance<T>>) -> BTreeMap<TokenId<T>, u128> {
+            if bonus.weighted == 0 {
+                return BTreeMap::new();
+            }
+            bonus
+                .profit
+                .iter()
+                .map(|(token_id, profit)| {
+                    let profit: u128 = (*profit).into();
+                    let r = U256::from(profit) * U256::from(REWARD_PER_STAKE_SCALE)
+                        / U256::from(bonus.weighted);
+                    (*token_id, r.try_into().unwrap_or(u128::MAX))
+                })
+                .collect()
+        }
+
+        fn accumulate(
+            accumulated: &mut BTreeMap<TokenId<T>, u128>,
+            per_stake: &BTreeMap<TokenId<T>, u128>,
+        ) {
+            for (token_id, r) in per_stake.iter() {
+                accumulated
+                    .entry(*token_id)
+                    .and_modify(|a| *a = a.saturating_add(*r))
+                    .or_insert(*r);
+            }
+        }
+
+        /// the `from` season is held by `weighted` while the seasons after it
+        /// until the `accumulated` one are held by the full amount
+        fn shares_of(
+            amount: Balance<T>,
+            weighted: u128,
+            from: &SeasonReward<TokenId<T>>,
+            accumulated: &BTreeMap<TokenId<T>, u128>,
+        ) -> BTreeMap<TokenId<T>, u128> {
+            let amount: u128 = amount.into();
+            let full = U256::from(amount) * U256::from(Self::season_duration());
+            let scale = U256::from(REWARD_PER_STAKE_SCALE);
+            accumulated
+                .iter()
+                .map(|(token_id, a)| {
+                    let since =
+                        a.saturating_sub(from.accumulated.get(token_id).copied().unwrap_or(0));
+                    let own = from.per_stake.get(token_id).copied().unwrap_or(0);
+                    let share = full
+                        .saturating_mul(U256::from(since))
+                        .saturating_add(U256::from(weighted).saturating_mul(U256::from(own)))
+                        / scale;
+                    (*token_id, share.try_into().unwrap_or(u128::MAX))
+                })
+                .filter(|(_, share)| *share != 0)
+                .collect()
+        }
+
+        /// accumulate the profit per stake of an ended season,
+        /// the `Bonuses` of it is pruned if no staking starts from it
+        fn finalize_season(dominator: &T::AccountId, season: Season) {
+            let per_stake = Self::per_stake_of(&Bonuses::<T>::get(dominator, season));
+            let accumulated = AccumulatedRewards::<T>::mutate(dominator, |a| {
+                Self::accumulate(a, &per_stake);
+                a.clone()
+            });
+            if StakingCounts::<T>::get(dominator, season) == 0 {
+                Bonuses::<T>::remove(dominator, season);
+            } else {
+                SeasonRewards::<T>::insert(
+                    dominator,
+                    season,
+                    SeasonReward {
+                        per_stake,
+                        accumulated,
+                    },
+                );
+            }
+        }
+
+        /// a staking moves from `season`, prune it if it has been finalized and not referenced
+        fn release_season(dominator: &T::AccountId, season: Season, current_season: Season) {
+            let count = StakingCounts::<T>::mutate_exists(dominator, season, |c| {
+                let count = c.unwrap_or_default().saturating_sub(1);
+                *c = Some(count).filter(|c| *c != 0);
+                count
+            });
+            if count == 0 && season < current_season {
+                SeasonRewards::<T>::remove(dominator, season);
+                Bonuses::<T>::remove(dominator, season);
+            }
+        }
+
+        /// accumulate the staking of a dominator until the latest season begins
+        pub(crate) fn snapshot_dominator(
+            dominator_id: &T::AccountId,
+            dominator: &Dominator<Balance<T>, T::BlockNumber>,
+            now: T::BlockNumber,
+        ) {
+            let start = dominator.start_from;
+            let season = Self::current_season(now, start);
+            if season == 0 {
+                return;
+            }
+            Self::checkpoint_staking(
+                dominator_id,
+                start,
+                dominator.staked,
+                Self::start_block_of_season(start, season),
+            );
+        }
+
+        fn progress_snapshots(budget: Weight) -> Weight {
+            let (round, last_key) = match SeasonCursor::<T>::get() {
+                Some(cursor) => cursor,
+                None => return Weight::from_ref_time(0u64),
+            };
+            let step = T::WeightInfo::snapshot_dominator();
+            let mut consumed = Weight::from_ref_time(0u64);
+            let mut stepped = false;
+            let mut iter = if last_key.is_empty() {
+                Dominators::<T>::iter()
+            } else {
+                Dominators::<T>::iter_from(last_key)
+            };
+            loop {
+                // always snapshot one dominator, or the cursor never advances under a tiny budget
+                if stepped && consumed.saturating_add(step) > budget {
+                    SeasonCursor::<T>::put((round, iter.last_raw_key().to_vec()));
+                    break;
+                }
+                match iter.next() {
+                    Some((id, dominator)) => {
+                        Self::snapshot_dominator(&id, &dominator, round);
+                        consumed = consumed.saturating_add(step);
+                        stepped = true;
+                    }
+                    None => {
+                        SeasonCursor::<T>::kill();
+                        break;
+                    }
+                }
+            }
+            consumed.saturating_add(RocksDbWeight::get().writes(1u64))
+        }
+
+        pub(crate) fn unlock_unbonding(
+            unlock_at: T::BlockNumber,
+            staker: T::AccountId,
+            amount: Balance<T>,
+        ) {
+            PendingUnstakings::<T>::remove(&unlock_at, &staker);
+            Unbondings::<T>::remove(&staker, &unlock_at);
+            let r = Self::unreserve(
+                RESERVE_FOR_PENDING_UNSTAKE,
+                staker.clone(),
+                T::Asset::native_token_id(),
+                amount,
+                &Self::system_account(),
+            );
+            if r.is_err() {
+                log::error!(
+                    "No enough tokens of {:?} to unlock, check onchain storage.",
+                    staker
+                );
+            } else {
+                Self::deposit_event(Event::TaoUnstakeUnlock(staker, amount));
+            }
+        }
+
+        fn progress_unlocks(now: T::BlockNumber, budget: Weight) -> Weight {
+            let period = T::DominatorCheckGracePeriod::get();
+            let mut cursor = UnlockCursor::<T>::get().unwrap_or_else(|| now - now % period);
+            if cursor > now {
+                return Weight::from_ref_time(0u64);
+            }
+            let step = T::WeightInfo::unlock_unbonding();
+            let mut consumed = Weight::from_ref_time(0u64);
+            while cursor <= now {
+                if consumed.saturating_add(step) > budget {
+                    break;
+                }
+                consumed = consumed.saturating_add(RocksDbWeight::get().reads(1u64));
+                match PendingUnstakings::<T>::iter_prefix(&cursor).next() {
+                    Some((staker, amount)) => {
+                        Self::unlock_unbonding(cursor, staker, amount);
+                        consumed = consumed.saturating_add(step);
+                    }
+                    None => cursor += period,
+                }
+            }
+            UnlockCursor::<T>::put(cursor);
+            consumed.saturating_add(RocksDbWeight::get().writes(1u64))
+        }
+
+        /// the legacy shares were calculated by the staking snapshot of each season ends
+        fn migrate_to_weighted_staking() -> Weight {
+            let duration = Self::season_duration();
+            let mut count = 0u64;
+            Bonuses::<T>::translate::<BonusV0<TokenId<T>, Balance<T>>, _>(|_, _, old| {
+                count += 1;
+                let staked: u128 = old.staked.into();
+                Some(Bonus {
+                    staked: old.staked,
+                    weighted: staked.saturating_mul(duration),
+                    profit: old.profit,
+                })
+            });
+            Stakings::<T>::translate::<StakingV0<Balance<T>>, _>(|_, _, old| {
+                count += 1;
+                let amount: u128 = old.amount.into();
+                Some(Staking {
+                    from_season: old.from_season,
+                    amount: old.amount,
+                    weighted: amount.saturating_mul(duration),
+                })
+            });
+            let now = frame_system::Pallet::<T>::block_number();
+            for (id, dominator) in Dominators::<T>::iter() {
+                count += 1;
+                let season = Self::current_season(now, dominator.start_from);
+                StakingCheckpoints::<T>::insert(
+                    &id,
+                    Self::start_block_of_season(dominator.start_from, season),
+                );
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        /// build the per-staker index of `PendingUnstakings`
+        fn migrate_to_indexed_unbondings() -> Weight {
+            let mut count = 0u64;
+            for (unlock_at, staker, amount) in PendingUnstakings::<T>::iter() {
+                count += 1;
+                Unbondings::<T>::insert(&staker, &unlock_at, amount);
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        /// replace the historical `Bonuses` with the accumulated rewards
+        fn migrate_to_accumulated_rewards() -> Weight {
+            let mut count = 0u64;
+            for (dominator, _, staking) in Stakings::<T>::iter() {
+                count += 1;
+                if !staking.amount.is_zero() {
+                    StakingCounts::<T>::mutate(&dominator, staking.from_season, |c| *c += 1);
+                }
+            }
+            for (id, dominator) in Dominators::<T>::iter() {
+                count += 1;
+                let checkpoint = StakingCheckpoints::<T>::get(&id).unwrap_or(dominator.start_from);
+                let finalized = Self::current_season(checkpoint, dominator.start_from);
+                let mut seasons = Bonuses::<T>::iter_key_prefix(&id)
+                    .filter(|season| *season < finalized)
+                    .collect::<Vec<_>>();
+                seasons.sort();
+                for season in seasons {
+                    count += 1;
+                    Self::finalize_season(&id, season);
+                }
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        fn migrate_to_authorized_totals() -> Weight {
+            let mut count = 0u64;
+            for ((reserve_id, _, token_id), dominator, reserved) in Reserves::<T>::iter() {
+                count += 1;
+                if reserve_id == RESERVE_FOR_AUTHORIZING {
+                    AuthorizedTotals::<T>::mutate(&dominator, token_id, |t| {
+                        *t = t.saturating_add(reserved)
+                    });
+                }
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        fn migrate_to_indexed_names() -> Weight {
+            let mut count = 0u64;
+            for (id, dominator) in Dominators::<T>::iter() {
+                count += 1;
+                let name = Self::normalize_name(&dominator.name).unwrap_or(dominator.name);
+                if !DominatorByName::<T>::contains_key(&name) {
+                    DominatorByName::<T>::insert(&name, &id);
+                }
+            }
+            RocksDbWeight::get().reads_writes(count.saturating_mul(2), count)
+        }
+
+        fn migrate_to_versioned_x25519_keys() -> Weight {
+            let mut count = 0u64;
+            for (dominator, setting) in DominatorSettings::<T>::iter() {
+                count += 1;
+                if !setting.x25519_pubkey.is_empty() {
+                    X25519Keys::<T>::insert(
+                        &dominator,
+                        vec![X25519Key {
+                            version: 0,
+                            key: setting.x25519_pubkey,
+                            activate_at: Zero::zero(),
+                        }],
+                    );
+                }
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        /// the keys in `DominatorSettings` were never rotated, clear them to avoid being misread
+        fn clear_legacy_x25519_keys() -> Weight {
+            let mut count = 0u64;
+            DominatorSettings::<T>::translate_values::<DominatorSetting<T::AccountId>, _>(
+                |mut setting| {
+                    count += 1;
+                    setting.x25519_pubkey.clear();
+                    Some(setting)
+                },
+            );
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        fn migrate_to_staked_dominators() -> Weight {
+            let mut count = 0u64;
+            for (dominator, staker, staking) in Stakings::<T>::iter() {
+                count += 1;
+                if !staking.amount.is_zero() {
+                    StakedDominators::<T>::insert(&staker, &dominator, ());
+                }
+            }
+            RocksDbWeight::get().reads_writes(count, count)
+        }
+
+        pub(crate) fn validate_rpc_endpoint(rpc_endpoint: &[u8]) -> DispatchResult {
+            if rpc_endpoint.is_empty() {
+                return Ok(());
+            }
+            ensure!(
+                rpc_endpoint.len() <= MAX_RPC_ENDPOINT_LEN,
+                Error::<T>::InvalidRpcEndpoint
+            );
+            let endpoints =
+                AsciiStr::from_ascii(rpc_endpoint).map_err(|_| Error::<T>::InvalidRpcEndpoint)?;
+            let endpoints = endpoints.as_str().split(',').collect::<Vec<_>>();
+            ensure!(
+                endpoints.len() <= MAX_RPC_ENDPOINTS,
+                Error::<T>::InvalidRpcEndpoint
+            );
+            for endpoint in endpoints {
+                let endpoint = endpoint.trim();
+                let host = endpoint
+                    .strip_prefix("wss://")
+                    .or_else(|| endpoint.strip_prefix("ws://"))
+                    .ok_or(Error::<T>::InvalidRpcEndpoint)?;
+                ensure!(
+                    !host.is_empty() && !host.contains(char::is_whitespace),
+                    Error::<T>::InvalidRpcEndpoint
+                );
+            }
+            Ok(())
+        }
+
+        /// a valid name is 2~32 ascii characters, compared case-insensitively
+        pub(crate) fn normalize_name(identifier: &[u8]) -> Result<Vec<u8>, DispatchError> {
+            let name = AsciiStr::from_ascii(identifier).map_err(|_| Error::<T>::InvalidName)?;
+            ensure!(name.len() >= 2 && name.len() <= 32, Error::<T>::InvalidName);
+            Ok(identifier.iter().map(|c| c.to_ascii_lowercase()).collect())
+        }
+
+        pub fn dominator_of_name(identifier: Vec<u8>) -> Option<T::AccountId> {
+            Self::normalize_name(&identifier)
+                .ok()
+                .and_then(|name| DominatorByName::<T>::get(&name))
+        }
+
+        pub(crate) fn checkpoint_root(
+            dominator_id: &T::AccountId,
+            event_id: u64,
+            now: T::BlockNumber,
+            root: MerkleHash,
+        ) {
+            let max = T::MaxRootCheckpoints::get() as usize;
+            if max == 0 {
+                return;
+            }
+            let mut checkpoints = RootCheckpoints::<T>::get(dominator_id);
+            let due = checkpoints
+                .last()
+                .map(|c| event_id >= c.event_id.saturating_add(T::RootCheckpointInterval::get()))
+                .unwrap_or(true);
+            if !due {
+                return;
+            }
+            if checkpoints.len() >= max {
+                checkpoints.drain(..checkpoints.len() + 1 - max);
+            }
+            checkpoints.push(RootCheckpoint {
+                event_id,
+                block_number: now,
+                root,
+            });
+            RootCheckpoints::<T>::insert(dominator_id, checkpoints);
+        }
+
+        pub fn is_paused(dominator: &T::AccountId, flags: u8) -> bool {
+            (GlobalPause::<T>::get() | DominatorPauses::<T>::get(dominator)) & flags != 0
+        }
+
+        fn is_quote_supported(base: TokenId<T>, quote: TokenId<T>) -> bool {
+            T::Asset::is_stable(&quote) || ApprovedQuotes::<T>::get(base, quote)
+        }
+
+        /// convert the volume denominated in `quote` to the stable currencies by the price of `quote`
+        pub(crate) fn to_stable_volume(quote: TokenId<T>, vol: Balance<T>) -> Balance<T> {
+            if T::Asset::is_stable(&quote) {
+                return vol;
+            }
+            let price: u128 = T::Indicator::get_price(&quote).into();
+            let vol =
+                U256::from(vol.into()).saturating_mul(U256::from(price)) / U256::from(QUINTILL);
+            let vol: u128 = vol.try_into().unwrap_or(u128::MAX);
+            vol.into()
+        }
+
+        pub fn commission_of(dominator: &T::AccountId, now: T::BlockNumber) -> Permill {
+            match PendingCommissions::<T>::get(dominator) {
+                Some((pending, effective_at)) if now >= effective_at => pending.rate,
+                _ => DominatorCommissions::<T>::get(dominator)
+                    .map(|c| c.rate)
+                    .unwrap_or_default(),
+            }
+        }
+
+        fn apply_pending_commission(dominator: &T::AccountId, now: T::BlockNumber) {
+            if let Some((pending, effective_at)) = PendingCommissions::<T>::get(dominator) {
+                if now >= effective_at {
+                    PendingCommissions::<T>::remove(dominator);
+                    DominatorCommissions::<T>::insert(dominator, pending);
+                }
+            }
+        }
+
+        /// charge the commission to the beneficiary of dominator and put the rest into the bonus
+        #[transactional]
+        fn share_fee(
+            dominator: &T::AccountId,
+            season: Season,
+            currency: TokenId<T>,
+            fee: Balance<T>,
+        ) -> DispatchResult {
+            if fee.is_zero() {
+                return Ok(());
+            }
+            let rate = Self::commission_of(dominator, frame_system::Pallet::<T>::block_number());
+            let commission: Balance<T> = rate.mul_floor::<u128>(fee.into()).into();
+            if !commission.is_zero() {
+                let beneficiary = DominatorSettings::<T>::get(dominator)
+                    .and_then(|s| s.beneficiary)
+                    .unwrap_or(dominator.clone());
+                T::Asset::try_mutate_account(&currency, &beneficiary, |b| Ok(b.0 += commission))?;
+            }
+            Self::put_profit(dominator, season, currency, fee - commission)
+        }
+
         #[transactional]
         fn put_profit(
             dominator: &T::AccountId,
@@ -2198,12 +3953,67 @@ pub mod pallet {
             Self::current_season(now, claim_at)
         }
 
+        /// the authorized totals of all tokens with the latest root and sequence of a dominator,
+        /// which could be cross-checked with the inclusion proofs of the leaves
+        pub fn proof_of_reserves_of(
+            dominator: T::AccountId,
+        ) -> (
+            Vec<(TokenId<T>, Balance<T>)>,
+            MerkleHash,
+            u64,
+            T::BlockNumber,
+        ) {
+            let mut totals = AuthorizedTotals::<T>::iter_prefix(&dominator)
+                .filter(|(_, total)| !total.is_zero())
+                .collect::<Vec<_>>();
+            totals.sort_by_key(|t| t.0);
+            let (root, (event_id, block_number)) = Dominators::<T>::get(&dominator)
+                .map(|d| (d.merkle_root, d.sequence))
+                .unwrap_or_default();
+            (totals, root, event_id, block_number)
+        }
+
+        /// the current key and the next one if it isn't activated at `now`
+        pub(crate) fn x25519_keys_at(
+            dominator: &T::AccountId,
+            now: T::BlockNumber,
+        ) -> Vec<X25519Key<T::BlockNumber>> {
+            let mut keys = X25519Keys::<T>::get(dominator);
+            if keys.len() > 1 && keys[1].activate_at <= now {
+                keys.remove(0);
+            }
+            keys
+        }
+
+        pub fn x25519_keys_of(dominator: T::AccountId) -> Vec<(u32, Vec<u8>, T::BlockNumber)> {
+            Self::x25519_keys_at(&dominator, frame_system::Pallet::<T>::block_number())
+                .into_iter()
+                .map(|k| (k.version, k.key, k.activate_at))
+                .collect()
+        }
+
+        pub fn root_checkpoints_of(
+            dominator: T::AccountId,
+        ) -> Vec<(u64, T::BlockNumber, MerkleHash)> {
+            RootCheckpoints::<T>::get(&dominator)
+                .into_iter()
+                .map(|c| (c.event_id, c.block_number, c.root))
+                .collect()
+        }
+
+        /// the pending unstakings of a staker ordered by the unlock block
+        pub fn unbonding_chunks_of(staker: T::AccountId) -> Vec<(T::BlockNumber, Balance<T>)> {
+            let mut chunks = Unbondings::<T>::iter_prefix(&staker).collect::<Vec<_>>();
+            chunks.sort_by_key(|c| c.0);
+            chunks
+        }
+
         pub fn pending_shares_of_dominator(
             dominator: T::AccountId,
             who: T::AccountId,
         ) -> Balance<T> {
-            let start_from = Dominators::<T>::try_get(&dominator)
-                .map(|d| d.start_from)
+            let (start_from, staked) = Dominators::<T>::try_get(&dominator)
+                .map(|d| (d.start_from, d.staked))
                 .unwrap_or_default();
             if start_from == Zero::zero() {
                 return Zero::zero();
@@ -2211,22 +4021,39 @@ pub mod pallet {
             let current_block = frame_system::Pallet::<T>::block_number();
             let current_season = Self::current_season(current_block, start_from);
             let staking = Stakings::<T>::get(&dominator, &who);
-            if staking.amount.is_zero() {
+            if staking.amount.is_zero() || staking.from_season == current_season {
                 return Zero::zero();
             }
-            let user_staking: u128 = staking.amount.into();
-            let mut shares = 0u128;
-            for season in staking.from_season..current_season {
-                let bonus = Bonuses::<T>::get(&dominator, season);
-                if bonus.staked.is_zero() || bonus.profit.is_empty() {
-                    continue;
-                }
-                let total_staking: u128 = bonus.staked.into();
-                let r: Perquintill = Perquintill::from_rational(user_staking, total_staking);
-                for (_, profit) in bonus.profit.into_iter() {
-                    shares += r * profit.into();
+            // finalize the ended seasons which haven't been checkpointed
+            let uncheckpointed: BTreeMap<Season, u128> =
+                Self::uncheckpointed_staking(&dominator, start_from, staked, current_block)
+                    .into_iter()
+                    .collect();
+            let checkpoint = StakingCheckpoints::<T>::get(&dominator).unwrap_or(start_from);
+            let mut accumulated = AccumulatedRewards::<T>::get(&dominator);
+            let mut from = SeasonRewards::<T>::get(&dominator, staking.from_season);
+            for season in Self::current_season(checkpoint, start_from)..current_season {
+                let mut bonus = Bonuses::<T>::get(&dominator, season);
+                bonus.weighted = bonus
+                    .weighted
+                    .saturating_add(uncheckpointed.get(&season).copied().unwrap_or_default());
+                let per_stake = Self::per_stake_of(&bonus);
+                Self::accumulate(&mut accumulated, &per_stake);
+                if season == staking.from_season {
+                    from = Some(SeasonReward {
+                        per_stake,
+                        accumulated: accumulated.clone(),
+                    });
                 }
             }
+            let shares: u128 = Self::shares_of(
+                staking.amount,
+                staking.weighted,
+                &from.unwrap_or_default(),
+                &accumulated,
+            )
+            .into_values()
+            .fold(0u128, |acc, share| acc.saturating_add(share));
             shares.into()
         }
     }
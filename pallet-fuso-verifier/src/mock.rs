@@ -1,15 +1,15 @@
 use crate as pallet_fuso_verifier;
-use frame_support::traits::{ConstU32, SortedMembers};
-use frame_support::{construct_runtime, parameter_types};
+use frame_support::traits::{ConstU32, EitherOfDiverse, SortedMembers};
+use frame_support::{construct_runtime, parameter_types, weights::Weight};
 use frame_system as system;
-use frame_system::EnsureSignedBy;
+use frame_system::{EnsureRoot, EnsureSignedBy};
 use fuso_support::ChainId;
 use sp_keyring::AccountKeyring;
 use sp_runtime::traits::{IdentifyAccount, Verify};
 use sp_runtime::{
     generic,
     traits::{AccountIdLookup, BlakeTwo256},
-    MultiSignature,
+    MultiSignature, Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -118,6 +118,17 @@ parameter_types! {
     pub const DominatorCheckGracePeriod: BlockNumber = 10;
     pub const MaxMakerFee: u32 = 10000;
     pub const MaxTakerFee: u32 = 10000;
+    pub const AuthorizingExpiry: BlockNumber = 100;
+    pub const CommissionUpdateInterval: BlockNumber = 100;
+    pub const MaxCommission: Permill = Permill::from_percent(50);
+    pub const RedelegateCooldown: BlockNumber = 100;
+    pub const UnbondingDelay: BlockNumber = 14400 * 4;
+    pub const RootCheckpointInterval: u64 = 3;
+    pub const MaxRootCheckpoints: u32 = 2;
+    pub const RenameFee: Balance = 100;
+    pub const X25519KeyRotationDelay: BlockNumber = 10;
+    pub static BookkeepingWeightLimit: Weight = Weight::from_ref_time(u64::MAX);
+    pub const TreasuryAccount: AccountId = TREASURY;
 }
 
 pub struct PhantomData;
@@ -153,18 +164,34 @@ impl pallet_fuso_indicator::Config for Test {
 
 impl pallet_fuso_verifier::Config for Test {
     type Asset = TokenModule;
+    type AuthorizingExpiry = AuthorizingExpiry;
+    type BookkeepingWeightLimit = BookkeepingWeightLimit;
     type BrokerStakingThreshold = BrokerStakingThreshold;
     type Callback = RuntimeCall;
+    type CommissionUpdateInterval = CommissionUpdateInterval;
+    type DominatorAdminOrigin =
+        EitherOfDiverse<EnsureRoot<AccountId>, EnsureSignedBy<TreasuryMembers, AccountId>>;
     type DominatorCheckGracePeriod = DominatorCheckGracePeriod;
     type DominatorOnlineThreshold = DominatorOnlineThreshold;
     type Indicator = Indicator;
+    type MarketAdminOrigin = EnsureRoot<AccountId>;
+    type MaxCommission = MaxCommission;
     type MaxMakerFee = MaxMakerFee;
+    type MaxRootCheckpoints = MaxRootCheckpoints;
     type MaxTakerFee = MaxTakerFee;
     type MinimalStakingAmount = MinimalStakingAmount;
+    type PauseOrigin = EnsureSignedBy<TreasuryMembers, AccountId>;
+    type RedelegateCooldown = RedelegateCooldown;
+    type RenameFee = RenameFee;
     type Rewarding = PhantomData;
+    type RootCheckpointInterval = RootCheckpointInterval;
     type RuntimeEvent = RuntimeEvent;
     type SeasonDuration = SeasonDuration;
+    type SweepOrigin = EnsureRoot<AccountId>;
+    type TreasuryAccount = TreasuryAccount;
+    type UnbondingDelay = UnbondingDelay;
     type WeightInfo = ();
+    type X25519KeyRotationDelay = X25519KeyRotationDelay;
 }
 
 // Configure a mock runtime to test the pallet.
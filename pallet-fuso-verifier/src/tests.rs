@@ -1,15 +1,19 @@
 use super::*;
 use crate::mock::*;
 use crate::mock::{new_tester, AccountId};
+use crate::weights::WeightInfo;
 use crate::Error;
 use crate::Pallet;
 use frame_support::traits::{OnFinalize, OnInitialize};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    weights::{constants::RocksDbWeight, Weight},
+};
 use frame_system::RawOrigin;
-use fuso_support::traits::PriceOracle;
+use fuso_support::traits::{PriceOracle, ReservableToken, Token as _};
 use fuso_support::{constants::*, XToken};
 use sp_keyring::AccountKeyring;
-use sp_runtime::{traits::Zero, MultiAddress};
+use sp_runtime::{traits::Zero, MultiAddress, Permill};
 
 type Token = pallet_fuso_token::Pallet<Test>;
 type Indicator = pallet_fuso_indicator::Pallet<Test>;
@@ -212,6 +216,600 @@ pub fn test_stake_unstake_should_work() {
     });
 }
 
+#[test]
+pub fn test_shares_weighted_by_staking_duration() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        // TREASURY stakes the same amount in the middle of season 0
+        run_to_block(730);
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(TREASURY),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        run_to_block(1000);
+        crate::Bonuses::<Test>::mutate(&alice, 0, |b| {
+            b.profit.insert(1, 2160);
+        });
+        run_to_block(1450);
+        let bonus = Verifier::bonuses(&alice, 0);
+        assert_eq!(bonus.staked, 20000);
+        assert_eq!(bonus.weighted, 10000 * 1440 + 10000 * 720);
+        assert_eq!(Verifier::staking_counts(&alice, 0), 2);
+        assert_eq!(
+            Verifier::pending_shares_of_dominator(alice.clone(), ferdie.clone()),
+            1440
+        );
+        assert_ok!(Verifier::claim_shares(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_ok!(Verifier::claim_shares(
+            RuntimeOrigin::signed(TREASURY),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_eq!(Token::free_balance(&1, &ferdie), 1440);
+        assert_eq!(Token::free_balance(&1, &TREASURY), 720);
+        // season 0 is pruned after all stakings moved from it
+        assert_eq!(Verifier::staking_counts(&alice, 1), 2);
+        assert!(!crate::Bonuses::<Test>::contains_key(&alice, 0));
+        assert!(Verifier::season_rewards(&alice, 0).is_none());
+    });
+}
+
+#[test]
+pub fn test_auto_compound_and_redelegate() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let charlie: AccountId = AccountKeyring::Charlie.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(charlie.clone()),
+            b"fine".to_vec()
+        ));
+        for dominator in [&alice, &charlie] {
+            assert_ok!(Verifier::launch(
+                RawOrigin::Root.into(),
+                MultiAddress::Id(dominator.clone())
+            ));
+        }
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(TREASURY),
+            MultiAddress::Id(charlie.clone()),
+            10000
+        ));
+        assert_ok!(Verifier::set_auto_compound(
+            RuntimeOrigin::signed(ferdie.clone()),
+            Some(MultiAddress::Id(bob.clone()))
+        ));
+        assert_eq!(Verifier::auto_compounds(&ferdie), Some(bob.clone()));
+        run_to_block(1000);
+        crate::Bonuses::<Test>::mutate(&alice, 0, |b| {
+            b.profit.insert(0, 1440);
+            b.profit.insert(1, 720);
+        });
+        run_to_block(1450);
+        assert_ok!(Verifier::claim_shares(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone())
+        ));
+        // the native shares are restaked and the others go to the payee
+        assert_eq!(Token::free_balance(&1, &bob), 720);
+        assert_eq!(Verifier::stakings(&alice, &ferdie).amount, 11440);
+        assert_eq!(Verifier::dominators(&alice).unwrap().staked, 11440);
+        assert_eq!(
+            Verifier::reserves(&(RESERVE_FOR_STAKING, ferdie.clone(), 0u32), &alice),
+            11440
+        );
+        assert_noop!(
+            Verifier::redelegate(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                MultiAddress::Id(alice.clone()),
+                500
+            ),
+            Error::<Test>::InvalidRedelegation
+        );
+        assert_ok!(Verifier::redelegate(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            MultiAddress::Id(charlie.clone()),
+            500
+        ));
+        assert_eq!(Verifier::stakings(&alice, &ferdie).amount, 10940);
+        assert_eq!(Verifier::stakings(&charlie, &ferdie).amount, 500);
+        assert_eq!(Verifier::dominators(&charlie).unwrap().staked, 10500);
+        assert_eq!(
+            Verifier::reserves(&(RESERVE_FOR_STAKING, ferdie.clone(), 0u32), &charlie),
+            500
+        );
+        assert_eq!(Verifier::pending_unstakings(1450 + 14400 * 4, &ferdie), 0);
+        assert_noop!(
+            Verifier::redelegate(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                MultiAddress::Id(charlie.clone()),
+                500
+            ),
+            Error::<Test>::RedelegateTooFrequent
+        );
+        run_to_block(1550);
+        assert_ok!(Verifier::redelegate(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(charlie.clone()),
+            MultiAddress::Id(alice.clone()),
+            500
+        ));
+        assert_eq!(Verifier::stakings(&charlie, &ferdie).amount, 0);
+    });
+}
+
+#[test]
+pub fn test_rebond_pending_unstakings() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        assert_ok!(Verifier::unstake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            2000
+        ));
+        run_to_block(25);
+        assert_ok!(Verifier::unstake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            3000
+        ));
+        assert_eq!(
+            Verifier::unbonding_chunks_of(ferdie.clone()),
+            vec![(57610, 2000), (57620, 3000)]
+        );
+        assert_noop!(
+            Verifier::rebond(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                6000
+            ),
+            Error::<Test>::InsufficientUnbonding
+        );
+        // the latest chunk is rebonded first
+        assert_ok!(Verifier::rebond(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            4000
+        ));
+        assert_eq!(
+            Verifier::unbonding_chunks_of(ferdie.clone()),
+            vec![(57610, 1000)]
+        );
+        assert_eq!(Verifier::pending_unstakings(57610, &ferdie), 1000);
+        assert_eq!(Verifier::pending_unstakings(57620, &ferdie), 0);
+        assert_eq!(Verifier::stakings(&alice, &ferdie).amount, 9000);
+        assert_eq!(Verifier::dominators(&alice).unwrap().staked, 9000);
+        assert_eq!(
+            Verifier::reserves(
+                &(RESERVE_FOR_PENDING_UNSTAKE, ferdie.clone(), 0u32),
+                &Verifier::system_account(),
+            ),
+            1000
+        );
+        assert_eq!(Balance::reserved_balance(&ferdie), 10000);
+        run_to_block(57610);
+        assert!(Verifier::unbonding_chunks_of(ferdie.clone()).is_empty());
+        assert_eq!(Balance::reserved_balance(&ferdie), 9000);
+    });
+}
+
+#[test]
+pub fn test_unlocks_continue_when_weight_exhausted() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+        for staker in [&ferdie, &TREASURY] {
+            assert_ok!(Verifier::stake(
+                RuntimeOrigin::signed(staker.clone()),
+                MultiAddress::Id(alice.clone()),
+                10000
+            ));
+            assert_ok!(Verifier::unstake(
+                RuntimeOrigin::signed(staker.clone()),
+                MultiAddress::Id(alice.clone()),
+                500
+            ));
+        }
+        run_to_block(57609);
+        // only the snapshot of alice and one unlock fit into the limit
+        let db = RocksDbWeight::get();
+        BookkeepingWeightLimit::set(
+            &db.reads(3)
+                .saturating_add(db.writes(3))
+                .saturating_add(<() as WeightInfo>::snapshot_dominator())
+                .saturating_add(<() as WeightInfo>::unlock_unbonding()),
+        );
+        run_to_block(57610);
+        assert_eq!(
+            crate::PendingUnstakings::<Test>::iter_prefix(57610).count(),
+            1
+        );
+        run_to_block(57611);
+        assert_eq!(
+            crate::PendingUnstakings::<Test>::iter_prefix(57610).count(),
+            0
+        );
+        assert_eq!(Balance::reserved_balance(&ferdie), 9500);
+        assert_eq!(Balance::reserved_balance(&TREASURY), 9500);
+        assert!(Verifier::unbonding_chunks_of(ferdie.clone()).is_empty());
+    });
+}
+
+#[test]
+pub fn test_snapshots_progress_under_tiny_weight_limit() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(bob.clone()),
+            b"fine".to_vec()
+        ));
+        BookkeepingWeightLimit::set(&Weight::from_ref_time(0u64));
+        run_to_block(20);
+        let (round, last_key) = crate::SeasonCursor::<Test>::get().unwrap();
+        assert_eq!(round, 20);
+        assert!(!last_key.is_empty());
+        run_to_block(22);
+        assert!(crate::SeasonCursor::<Test>::get().is_none());
+    });
+}
+
+#[test]
+pub fn test_claim_all_shares_to_payee() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let charlie: AccountId = AccountKeyring::Charlie.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(charlie.clone()),
+            b"fine".to_vec()
+        ));
+        for dominator in [&alice, &charlie] {
+            assert_ok!(Verifier::launch(
+                RawOrigin::Root.into(),
+                MultiAddress::Id(dominator.clone())
+            ));
+            assert_ok!(Verifier::stake(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(dominator.clone()),
+                10000
+            ));
+        }
+        assert_ok!(Verifier::set_payee(
+            RuntimeOrigin::signed(ferdie.clone()),
+            Some(MultiAddress::Id(bob.clone()))
+        ));
+        assert_eq!(Verifier::payees(&ferdie), Some(bob.clone()));
+        run_to_block(1000);
+        for dominator in [&alice, &charlie] {
+            crate::Bonuses::<Test>::mutate(dominator, 0, |b| {
+                b.profit.insert(1, 720);
+            });
+        }
+        run_to_block(1450);
+        let staked = crate::StakedDominators::<Test>::iter_key_prefix(&ferdie).collect::<Vec<_>>();
+        assert_ok!(Verifier::claim_all_shares(
+            RuntimeOrigin::signed(ferdie.clone()),
+            1,
+            None
+        ));
+        assert_eq!(Token::free_balance(&1, &bob), 720);
+        System::assert_last_event(RuntimeEvent::Verifier(crate::Event::AllSharesClaimed(
+            ferdie.clone(),
+            Some(staked[0].clone()),
+        )));
+        // continue from the last claimed dominator
+        assert_ok!(Verifier::claim_all_shares(
+            RuntimeOrigin::signed(ferdie.clone()),
+            1,
+            Some(staked[0].clone())
+        ));
+        assert_eq!(Token::free_balance(&1, &bob), 1440);
+        System::assert_last_event(RuntimeEvent::Verifier(crate::Event::AllSharesClaimed(
+            ferdie.clone(),
+            Some(staked[1].clone()),
+        )));
+        assert_ok!(Verifier::claim_all_shares(
+            RuntimeOrigin::signed(ferdie.clone()),
+            10,
+            Some(staked[1].clone())
+        ));
+        System::assert_last_event(RuntimeEvent::Verifier(crate::Event::AllSharesClaimed(
+            ferdie.clone(),
+            None,
+        )));
+        assert_eq!(Token::free_balance(&1, &bob), 1440);
+        assert_eq!(Token::free_balance(&1, &ferdie), 0);
+        assert_eq!(Verifier::stakings(&alice, &ferdie).amount, 10000);
+        assert_eq!(Verifier::stakings(&charlie, &ferdie).amount, 10000);
+        assert_eq!(
+            crate::StakedDominators::<Test>::iter_key_prefix(&ferdie).count(),
+            2
+        );
+        assert_ok!(Verifier::unstake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        assert_eq!(Verifier::staked_dominators(&ferdie, &alice), None);
+        assert_eq!(Verifier::staked_dominators(&ferdie, &charlie), Some(()));
+    });
+}
+
+#[test]
+pub fn test_clearing_dust_could_be_swept() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(10);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        assert_ok!(Token::reserve(&1, &ferdie, 5000000000000000000));
+        crate::Reserves::<Test>::insert(
+            &(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 1u32),
+            &alice,
+            5000000000000000000,
+        );
+        // the tolerance of a 6-decimals token is 10^12
+        assert!(Verifier::has_authorized_exactly_on(
+            ferdie.clone(),
+            1,
+            4999999000000000000,
+            &alice
+        ));
+        assert!(!Verifier::has_authorized_exactly_on(
+            ferdie.clone(),
+            1,
+            4999998999999999999,
+            &alice
+        ));
+        // the tolerance of an 18-decimals token is still 10^11
+        crate::Reserves::<Test>::insert(
+            &(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 0u32),
+            &alice,
+            5000000000000000000,
+        );
+        assert!(Verifier::has_authorized_exactly_on(
+            ferdie.clone(),
+            0,
+            4999999900000000000,
+            &alice
+        ));
+        assert!(!Verifier::has_authorized_exactly_on(
+            ferdie.clone(),
+            0,
+            4999999899999999999,
+            &alice
+        ));
+        assert_ok!(Verifier::clear(
+            &ferdie,
+            &alice,
+            1,
+            4999999600000000000,
+            3000000000000000000
+        ));
+        assert_eq!(Token::reserved_balance(&1, &ferdie), 3000000000000000000);
+        assert_ok!(Verifier::clear(
+            &ferdie,
+            &alice,
+            1,
+            3000000100000000000,
+            3000000000000000000
+        ));
+        assert_eq!(
+            Verifier::dusts(&alice, 1),
+            crate::Dust {
+                surplus: 400000000000,
+                deficit: 100000000000,
+            }
+        );
+        assert_noop!(
+            Verifier::sweep_dust(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Verifier::sweep_dust(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone()),
+            1
+        ));
+        assert_eq!(Token::free_balance(&1, &TREASURY), 300000000000);
+        assert_noop!(
+            Verifier::sweep_dust(RawOrigin::Root.into(), MultiAddress::Id(alice.clone()), 1),
+            Error::<Test>::NoDustToSweep
+        );
+    });
+}
+
+#[test]
+pub fn test_dominator_set_commission() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_noop!(
+            Verifier::dominator_set_commission(
+                RuntimeOrigin::signed(alice.clone()),
+                Permill::from_percent(10)
+            ),
+            Error::<Test>::DominatorNotFound
+        );
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::dominator_set_commission(
+            RuntimeOrigin::signed(alice.clone()),
+            Permill::from_percent(10)
+        ));
+        assert_eq!(
+            Verifier::dominator_commissions(&alice),
+            Some(Commission {
+                rate: Permill::from_percent(10),
+                updated_at: 15,
+            })
+        );
+        assert_noop!(
+            Verifier::dominator_set_commission(
+                RuntimeOrigin::signed(alice.clone()),
+                Permill::from_percent(20)
+            ),
+            Error::<Test>::CommissionUpdateTooFrequent
+        );
+        run_to_block(115);
+        assert_noop!(
+            Verifier::dominator_set_commission(
+                RuntimeOrigin::signed(alice.clone()),
+                Permill::from_percent(60)
+            ),
+            Error::<Test>::CommissionTooHigh
+        );
+        // the raise takes effect from the next season
+        assert_ok!(Verifier::dominator_set_commission(
+            RuntimeOrigin::signed(alice.clone()),
+            Permill::from_percent(20)
+        ));
+        assert_eq!(
+            Verifier::dominator_commissions(&alice).map(|c| c.rate),
+            Some(Permill::from_percent(10))
+        );
+        assert_eq!(
+            Verifier::pending_commissions(&alice),
+            Some((
+                Commission {
+                    rate: Permill::from_percent(20),
+                    updated_at: 115,
+                },
+                1455
+            ))
+        );
+        assert_eq!(
+            Verifier::commission_of(&alice, 1454),
+            Permill::from_percent(10)
+        );
+        assert_eq!(
+            Verifier::commission_of(&alice, 1455),
+            Permill::from_percent(20)
+        );
+        run_to_block(1455);
+        // the decrease takes effect immediately
+        assert_ok!(Verifier::dominator_set_commission(
+            RuntimeOrigin::signed(alice.clone()),
+            Permill::from_percent(5)
+        ));
+        assert_eq!(
+            Verifier::dominator_commissions(&alice),
+            Some(Commission {
+                rate: Permill::from_percent(5),
+                updated_at: 1455,
+            })
+        );
+        assert_eq!(Verifier::pending_commissions(&alice), None);
+        assert_eq!(Verifier::dominator_commissions(&bob), None);
+    });
+}
+
 #[test]
 pub fn test_authorize_should_work() {
     new_tester().execute_with(|| {
@@ -225,153 +823,989 @@ pub fn test_authorize_should_work() {
             true,
             6,
         );
-
+
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        // assert_ok!(Token::issue(
+        //     RuntimeOrigin::signed(ferdie.clone()),
+        //     10000000000000000000,
+        //     br#"USDT"#.to_vec()
+        // ));
+        let token_info = Token::get_token_info(1);
+        assert!(token_info.is_some());
+        let token_info: XToken<u128> = token_info.unwrap();
+        match token_info {
+            XToken::NEP141(_, _, total, _, _) => {
+                assert_eq!(total, 10000000000000000000);
+            }
+            _ => {}
+        }
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                500000000000
+            ),
+            Error::<Test>::DominatorInactive
+        );
+        assert_noop!(
+            Verifier::stake(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                10000
+            ),
+            Error::<Test>::DominatorStatusInvalid
+        );
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                5000000000000000000000000
+            ),
+            Error::<Test>::DominatorInactive
+        );
+
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                500000000000
+            ),
+            Error::<Test>::DominatorInactive
+        );
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            10000
+        ));
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                5000000000000000000000000
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+        let reserves = Verifier::reserves(&(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 1u32), &alice);
+        assert_eq!(reserves, 0);
+
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                0,
+                5000000000000000000000000
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_ok!(Verifier::authorize(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            100000
+        ));
+        let reserves = Verifier::reserves(
+            &(RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1u32),
+            &alice,
+        );
+        assert_eq!(reserves, 100000);
+        let t = Verifier::reserves(
+            (RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1),
+            alice.clone(),
+        );
+        assert_eq!(t, 100000);
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                1000000
+            ),
+            Error::<Test>::ReceiptAlreadyExists
+        );
+        let t = Verifier::reserves(
+            (RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1),
+            alice.clone(),
+        );
+        assert_eq!(t, 100000);
+    });
+}
+
+#[test]
+pub fn test_set_price_should_work() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            false,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        Indicator::set_price(1, 9_000_000, 3_000_000, 3333);
+        let r = Indicator::get_price(&1u32.into());
+        assert_eq!(r, 333333333333333333);
+    });
+}
+
+#[test]
+pub fn test_non_stable_quote_should_be_approved() {
+    new_tester().execute_with(|| {
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        let btc = XToken::NEP141(
+            br#"BTC"#.to_vec(),
+            br#"btc.testnet"#.to_vec(),
+            Zero::zero(),
+            false,
+            8,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), btc));
+        assert_noop!(
+            Verifier::approve_quote(RuntimeOrigin::signed(ferdie.clone()), 0, 2, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Verifier::approve_quote(RawOrigin::Root.into(), 2, 2, true),
+            Error::<Test>::IllegalParameters
+        );
+        assert_noop!(
+            Verifier::approve_quote(RawOrigin::Root.into(), 0, 2, true),
+            Error::<Test>::QuoteNotPriced
+        );
+        // 1 BTC = 20000 USDT
+        Indicator::set_price(2, 1_000_000, 20_000_000_000, 15);
+        assert_ok!(Verifier::approve_quote(RawOrigin::Root.into(), 0, 2, true));
+        assert!(Verifier::approved_quotes(0, 2));
+        assert!(!Verifier::approved_quotes(1, 2));
+        assert_eq!(Verifier::to_stable_volume(2, 3_000), 60_000_000);
+        assert_eq!(Verifier::to_stable_volume(1, 3_000), 3_000);
+        assert_ok!(Verifier::approve_quote(RawOrigin::Root.into(), 0, 2, false));
+        assert!(!Verifier::approved_quotes(0, 2));
+    });
+}
+
+#[test]
+pub fn test_revoke_should_work() {
+    new_tester().execute_with(|| {
+        run_to_block(100);
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        // assert_ok!(Token::issue(
+        //     RuntimeOrigin::signed(ferdie.clone()),
+        //     10000000000000000000,
+        //     br#"USDT"#.to_vec()
+        // ));
+        let token_info = Token::get_token_info(1);
+        assert!(token_info.is_some());
+        let token_info: XToken<u128> = token_info.unwrap();
+        match token_info {
+            XToken::NEP141(_, _, total, _, _) => {
+                assert_eq!(total, 10000000000000000000);
+            }
+            _ => {}
+        }
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            800000000000
+        ));
+        run_to_block(1000);
+        assert_ok!(Verifier::authorize(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000
+        ));
+        assert_eq!(
+            Verifier::receipts(alice.clone(), ferdie.clone()),
+            Some(Receipt::Authorize(1, 500000000000u128.into(), 1000))
+        );
+        use codec::Encode;
+        let mut states = GlobalStates::default();
+        let key = [&[0x00][..], &ferdie.encode()[..], &u32::to_le_bytes(1)[..]].concat();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![Proof {
+                event_id: 1,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferIn(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+
+        assert_ok!(Verifier::revoke_with_callback(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000,
+            Box::new(crate::mock::RuntimeCall::Balances(pallet_balances::Call::<
+                Test,
+            >::transfer {
+                dest: MultiAddress::Id(bob.clone()),
+                value: 500000000000u128.into(),
+            })),
+        ));
+        assert_eq!(
+            Verifier::receipts(alice.clone(), ferdie.clone()),
+            Some(Receipt::RevokeWithCallback(
+                1,
+                500000000000u128.into(),
+                1000,
+                crate::mock::RuntimeCall::Balances(pallet_balances::Call::<Test>::transfer {
+                    dest: MultiAddress::Id(bob.clone()),
+                    value: 500000000000u128.into(),
+                })
+            ))
+        );
+        // let call = crate::mock::Call::Balances(pallet_balances::Call::<Test>::transfer {
+        //     dest: MultiAddress::Id(bob.clone()),
+        //     value: 500000000000u128.into(),
+        // });
+
+        // frame_support::dispatch::Dispatchable::dispatch(
+        //     call,
+        //     RawOrigin::Signed(ferdie.clone()).into(),
+        // )
+        // .unwrap();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            new_v: [0u8; 32],
+            old_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![Proof {
+                event_id: 2,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferOut(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+        run_to_block(1002);
+        assert_eq!(Balances::free_balance(bob.clone()), 500000000000);
+    });
+}
+
+#[test]
+pub fn test_migrate_authorization_should_work() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let charlie: AccountId = AccountKeyring::Charlie.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(charlie.clone()),
+            b"fine".to_vec()
+        ));
+        for dominator in [&alice, &charlie] {
+            assert_ok!(Verifier::launch(
+                RawOrigin::Root.into(),
+                MultiAddress::Id(dominator.clone())
+            ));
+            assert_ok!(Verifier::stake(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(dominator.clone()),
+                800000000000
+            ));
+        }
+        run_to_block(1000);
+        assert_ok!(Verifier::authorize(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000
+        ));
+        use codec::Encode;
+        let mut states = GlobalStates::default();
+        let key = [&[0x00][..], &ferdie.encode()[..], &u32::to_le_bytes(1)[..]].concat();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![Proof {
+                event_id: 1,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferIn(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        assert_noop!(
+            Verifier::migrate_authorization(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                500000000000
+            ),
+            Error::<Test>::InvalidMigration
+        );
+        assert_ok!(Verifier::migrate_authorization(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            MultiAddress::Id(charlie.clone()),
+            1,
+            500000000000
+        ));
+        assert_eq!(
+            Verifier::receipts(alice.clone(), ferdie.clone()),
+            Some(Receipt::Revoke(1, 500000000000u128.into(), 1000))
+        );
+        assert_eq!(
+            Verifier::pending_migrations(&alice, &ferdie),
+            Some(charlie.clone())
+        );
+        assert_eq!(Verifier::authorized_totals(&alice, 1), 500000000000);
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            new_v: [0u8; 32],
+            old_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![Proof {
+                event_id: 2,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferOut(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        // the funds are kept reserved and stashed to the destination
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+        assert_eq!(Verifier::pending_migrations(&alice, &ferdie), None);
+        assert_eq!(
+            Verifier::receipts(charlie.clone(), ferdie.clone()),
+            Some(Receipt::Authorize(1, 500000000000u128.into(), 1000))
+        );
+        assert_eq!(Token::reserved_balance(&1, &ferdie), 500000000000);
+        assert_eq!(
+            Verifier::reserves(&(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 1u32), &alice),
+            0
+        );
+        assert_eq!(
+            Verifier::reserves(
+                &(RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1u32),
+                &charlie
+            ),
+            500000000000
+        );
+        let mut states = GlobalStates::default();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(charlie.clone()),
+            vec![Proof {
+                event_id: 1,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferIn(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        assert_eq!(
+            Verifier::reserves(&(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 1u32), &charlie),
+            500000000000
+        );
+        assert_eq!(Verifier::authorized_totals(&alice, 1), 0);
+        assert_eq!(
+            Verifier::proof_of_reserves_of(charlie.clone()),
+            (
+                vec![(1, 500000000000)],
+                states.root().clone().into(),
+                1,
+                1000
+            )
+        );
+    });
+}
+
+#[test]
+pub fn test_root_checkpoints_should_be_bounded() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        for event_id in 1..=10u64 {
+            Verifier::checkpoint_root(&alice, event_id, event_id as u32 * 10, [event_id as u8; 32]);
+        }
+        // the interval is 3 and at most 2 checkpoints are kept
+        assert_eq!(
+            Verifier::root_checkpoints_of(alice.clone()),
+            vec![(7, 70, [7u8; 32]), (10, 100, [10u8; 32])]
+        );
+    });
+}
+
+#[test]
+pub fn test_pause_should_block_authorizing() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
+        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt));
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_ok!(Verifier::launch(
+            RawOrigin::Root.into(),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_ok!(Verifier::stake(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            800000000000
+        ));
+        assert_noop!(
+            Verifier::set_pause(RuntimeOrigin::signed(ferdie.clone()), None, PAUSE_ALL),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Verifier::set_pause(RuntimeOrigin::signed(TREASURY), None, 0b100),
+            Error::<Test>::IllegalParameters
+        );
+        assert_ok!(Verifier::set_pause(
+            RuntimeOrigin::signed(TREASURY),
+            None,
+            PAUSE_AUTHORIZE
+        ));
+        assert_noop!(
+            Verifier::authorize(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone()),
+                1,
+                500000000000
+            ),
+            Error::<Test>::CommandPaused
+        );
+        assert_ok!(Verifier::set_pause(
+            RuntimeOrigin::signed(TREASURY),
+            None,
+            0
+        ));
+        assert_ok!(Verifier::authorize(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000
+        ));
+        assert_ok!(Verifier::set_pause(
+            RuntimeOrigin::signed(TREASURY),
+            Some(MultiAddress::Id(alice.clone())),
+            PAUSE_ALL
+        ));
+        assert_eq!(Verifier::dominator_pauses(&alice), PAUSE_ALL);
+        assert!(Verifier::is_paused(&alice, PAUSE_TRADE));
+        use codec::Encode;
+        let mut states = GlobalStates::default();
+        let key = [&[0x00][..], &ferdie.encode()[..], &u32::to_le_bytes(1)[..]].concat();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        let transfer_in = Proof {
+            event_id: 1,
+            user_id: ferdie.clone(),
+            cmd: Command::TransferIn(1.into(), 500000000000.into()),
+            leaves,
+            maker_page_delta: 0,
+            maker_account_delta: 0,
+            merkle_proof: proof,
+            root: states.root().clone().into(),
+        };
+        assert_noop!(
+            Verifier::verify(
+                RuntimeOrigin::signed(alice.clone()),
+                vec![transfer_in.clone(); 101]
+            ),
+            Error::<Test>::ProofTooLarge
+        );
+        // the commands sequenced before pausing are still applied
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![transfer_in]
+        ));
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+        // and the users could always revoke
+        assert_ok!(Verifier::revoke(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000
+        ));
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: u128le_to_h256(500000000000, 0),
+            new_v: [0u8; 32],
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![Proof {
+                event_id: 2,
+                user_id: ferdie.clone(),
+                cmd: Command::TransferOut(1.into(), 500000000000.into()),
+                leaves,
+                maker_page_delta: 0,
+                maker_account_delta: 0,
+                merkle_proof: proof,
+                root: states.root().clone().into(),
+            }]
+        ));
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+        assert_eq!(Token::reserved_balance(&1, &ferdie), 0);
+        assert!(Verifier::is_paused(&alice, PAUSE_TRADE));
+    });
+}
+
+#[test]
+pub fn test_dominator_admin_origin_should_work() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_noop!(
+            Verifier::launch(
+                RuntimeOrigin::signed(ferdie.clone()),
+                MultiAddress::Id(alice.clone())
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Verifier::launch(
+            RuntimeOrigin::signed(TREASURY),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_eq!(
+            Verifier::dominators(&alice).unwrap().status,
+            DOMINATOR_INACTIVE
+        );
+        assert_noop!(
+            Verifier::evict(
+                RuntimeOrigin::signed(alice.clone()),
+                MultiAddress::Id(alice.clone())
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(Verifier::evict(
+            RuntimeOrigin::signed(TREASURY),
+            MultiAddress::Id(alice.clone())
+        ));
+        assert_eq!(
+            Verifier::dominators(&alice).unwrap().status,
+            DOMINATOR_EVICTED
+        );
+    });
+}
+
+#[test]
+pub fn test_dominator_names_should_be_indexed() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let charlie: AccountId = AccountKeyring::Charlie.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"Cool".to_vec()
+        ));
+        assert_noop!(
+            Verifier::register(RuntimeOrigin::signed(charlie.clone()), b"cOOL".to_vec()),
+            Error::<Test>::InvalidName
+        );
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(ferdie.clone()),
+            b"fine".to_vec()
+        ));
+        assert_eq!(
+            Verifier::dominator_of_name(b"COOL".to_vec()),
+            Some(alice.clone())
+        );
+        assert_noop!(
+            Verifier::rename(RuntimeOrigin::signed(alice.clone()), b"FINE".to_vec()),
+            Error::<Test>::InvalidName
+        );
+        assert_noop!(
+            Verifier::rename(RuntimeOrigin::signed(charlie.clone()), b"nice".to_vec()),
+            Error::<Test>::DominatorNotFound
+        );
+        let treasury = Balance::free_balance(&TREASURY);
+        assert_ok!(Verifier::rename(
+            RuntimeOrigin::signed(ferdie.clone()),
+            b"Nice".to_vec()
+        ));
+        assert_eq!(Balance::free_balance(&TREASURY), treasury + 100);
+        assert_eq!(
+            Verifier::dominators(&ferdie).unwrap().name,
+            b"Nice".to_vec()
+        );
+        assert_eq!(Verifier::dominator_of_name(b"fine".to_vec()), None);
+        assert_eq!(
+            Verifier::dominator_of_name(b"nice".to_vec()),
+            Some(ferdie.clone())
+        );
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(charlie.clone()),
+            b"fine".to_vec()
+        ));
+    });
+}
+
+#[test]
+pub fn test_x25519_key_should_rotate_after_delay() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let charlie: AccountId = AccountKeyring::Charlie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_noop!(
+            Verifier::dominator_set_pubkey(RuntimeOrigin::signed(charlie.clone()), vec![1; 32]),
+            Error::<Test>::DominatorNotFound
+        );
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        assert_noop!(
+            Verifier::dominator_set_pubkey(RuntimeOrigin::signed(alice.clone()), vec![1; 31]),
+            Error::<Test>::InvalidX25519Key
+        );
+        assert_ok!(Verifier::dominator_set_pubkey(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![1; 32]
+        ));
+        assert_eq!(
+            Verifier::x25519_keys_of(alice.clone()),
+            vec![(0, vec![1; 32], 15)]
+        );
+        assert_ok!(Verifier::dominator_set_pubkey(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![2; 32]
+        ));
+        assert_eq!(
+            Verifier::x25519_keys_of(alice.clone()),
+            vec![(0, vec![1; 32], 15), (1, vec![2; 32], 25)]
+        );
+        assert_eq!(Verifier::dominator_settings(&alice), None);
+        frame_system::Pallet::<Test>::set_block_number(25);
+        assert_eq!(
+            Verifier::x25519_keys_of(alice.clone()),
+            vec![(1, vec![2; 32], 25)]
+        );
+        assert_ok!(Verifier::dominator_set_pubkey(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![3; 32]
+        ));
+        assert_eq!(
+            Verifier::x25519_keys_of(alice.clone()),
+            vec![(1, vec![2; 32], 25), (2, vec![3; 32], 35)]
+        );
+    });
+}
+
+#[test]
+pub fn test_dominator_set_rpc_endpoint() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        assert_ok!(Verifier::register(
+            RuntimeOrigin::signed(alice.clone()),
+            b"cool".to_vec()
+        ));
+        for invalid in [
+            &b"http://a.example.com"[..],
+            &b"wss://"[..],
+            &b"wss://a.example.com,"[..],
+            &b"wss://a b"[..],
+            &b"ws://a,ws://b,ws://c,ws://d,ws://e"[..],
+        ] {
+            assert_noop!(
+                Verifier::dominator_set_rpc_endpoint(
+                    RuntimeOrigin::signed(alice.clone()),
+                    invalid.to_vec()
+                ),
+                Error::<Test>::InvalidRpcEndpoint
+            );
+        }
+        let endpoints = b"wss://a.example.com, ws://127.0.0.1:9944".to_vec();
+        assert_ok!(Verifier::dominator_set_rpc_endpoint(
+            RuntimeOrigin::signed(alice.clone()),
+            endpoints.clone()
+        ));
+        assert_eq!(
+            Verifier::dominator_settings(&alice).unwrap().rpc_endpoint,
+            endpoints
+        );
+        assert_eq!(Verifier::rpc_endpoint_updates(), vec![alice.clone()]);
+        run_to_block(16);
+        assert!(Verifier::rpc_endpoint_updates().is_empty());
+        assert_ok!(Verifier::dominator_set_rpc_endpoint(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![]
+        ));
+        assert!(Verifier::dominator_settings(&alice)
+            .unwrap()
+            .rpc_endpoint
+            .is_empty());
+    });
+}
+
+#[test]
+pub fn test_reclaim_expired_authorizing() {
+    new_tester().execute_with(|| {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let ferdie: AccountId = AccountKeyring::Ferdie.into();
+        frame_system::Pallet::<Test>::set_block_number(15);
+        let usdt = XToken::NEP141(
+            br#"USDT"#.to_vec(),
+            br#"usdt.testnet"#.to_vec(),
+            Zero::zero(),
+            true,
+            6,
+        );
         assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
         assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
-        // assert_ok!(Token::issue(
-        //     RuntimeOrigin::signed(ferdie.clone()),
-        //     10000000000000000000,
-        //     br#"USDT"#.to_vec()
-        // ));
-        let token_info = Token::get_token_info(1);
-        assert!(token_info.is_some());
-        let token_info: XToken<u128> = token_info.unwrap();
-        match token_info {
-            XToken::NEP141(_, _, total, _, _) => {
-                assert_eq!(total, 10000000000000000000);
-            }
-            _ => {}
-        }
         assert_ok!(Verifier::register(
             RuntimeOrigin::signed(alice.clone()),
             b"cool".to_vec()
         ));
-        assert_noop!(
-            Verifier::authorize(
-                RuntimeOrigin::signed(ferdie.clone()),
-                MultiAddress::Id(alice.clone()),
-                1,
-                500000000000
-            ),
-            Error::<Test>::DominatorInactive
-        );
-        assert_noop!(
-            Verifier::stake(
-                RuntimeOrigin::signed(ferdie.clone()),
-                MultiAddress::Id(alice.clone()),
-                10000
-            ),
-            Error::<Test>::DominatorStatusInvalid
-        );
-        assert_noop!(
-            Verifier::authorize(
-                RuntimeOrigin::signed(ferdie.clone()),
-                MultiAddress::Id(alice.clone()),
-                1,
-                5000000000000000000000000
-            ),
-            Error::<Test>::DominatorInactive
-        );
-
         assert_ok!(Verifier::launch(
             RawOrigin::Root.into(),
             MultiAddress::Id(alice.clone())
         ));
-
-        assert_noop!(
-            Verifier::authorize(
-                RuntimeOrigin::signed(ferdie.clone()),
-                MultiAddress::Id(alice.clone()),
-                1,
-                500000000000
-            ),
-            Error::<Test>::DominatorInactive
-        );
         assert_ok!(Verifier::stake(
             RuntimeOrigin::signed(ferdie.clone()),
             MultiAddress::Id(alice.clone()),
             10000
         ));
+        run_to_block(1000);
+        assert_ok!(Verifier::authorize(
+            RuntimeOrigin::signed(ferdie.clone()),
+            MultiAddress::Id(alice.clone()),
+            1,
+            500000000000
+        ));
+        assert_eq!(Token::reserved_balance(&1, &ferdie), 500000000000);
         assert_noop!(
-            Verifier::authorize(
-                RuntimeOrigin::signed(ferdie.clone()),
-                MultiAddress::Id(alice.clone()),
-                1,
-                5000000000000000000000000
-            ),
-            Error::<Test>::InsufficientBalance
-        );
-        let reserves = Verifier::reserves(&(RESERVE_FOR_AUTHORIZING, ferdie.clone(), 1u32), &alice);
-        assert_eq!(reserves, 0);
-
-        assert_noop!(
-            Verifier::authorize(
+            Verifier::reclaim_authorizing(
                 RuntimeOrigin::signed(ferdie.clone()),
                 MultiAddress::Id(alice.clone()),
-                0,
-                5000000000000000000000000
             ),
-            Error::<Test>::InsufficientBalance
+            Error::<Test>::AuthorizingNotExpired
         );
+        // the dominator can't accept the receipt after expired
+        run_to_block(1100);
+        let bob: AccountId = AccountKeyring::Bob.into();
+        assert_ok!(Token::do_mint(1, &bob, 10000000, None));
         assert_ok!(Verifier::authorize(
-            RuntimeOrigin::signed(ferdie.clone()),
+            RuntimeOrigin::signed(bob.clone()),
             MultiAddress::Id(alice.clone()),
             1,
-            100000
+            500000000000
         ));
-        let reserves = Verifier::reserves(
-            &(RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1u32),
-            &alice,
+        use codec::Encode;
+        let mut states = GlobalStates::default();
+        let key = [&[0x00][..], &ferdie.encode()[..], &u32::to_le_bytes(1)[..]].concat();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        let transfer_in = Proof {
+            event_id: 1,
+            user_id: ferdie.clone(),
+            cmd: Command::TransferIn(1.into(), 500000000000.into()),
+            leaves,
+            maker_page_delta: 0,
+            maker_account_delta: 0,
+            merkle_proof: proof,
+            root: states.root().clone().into(),
+        };
+        assert_noop!(
+            Verifier::verify(RuntimeOrigin::signed(alice.clone()), vec![transfer_in]),
+            Error::<Test>::AuthorizingExpired
         );
-        assert_eq!(reserves, 100000);
-        let t = Verifier::reserves(
-            (RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1),
+        // the prover rolls back and rejects it, which refunds the stash
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: [0u8; 32],
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        let reject_transfer_in = Proof {
+            event_id: 1,
+            user_id: ferdie.clone(),
+            cmd: Command::RejectTransferIn,
+            leaves,
+            maker_page_delta: 0,
+            maker_account_delta: 0,
+            merkle_proof: proof,
+            root: states.root().clone().into(),
+        };
+        let key = [&[0x00][..], &bob.encode()[..], &u32::to_le_bytes(1)[..]].concat();
+        let leaves = vec![MerkleLeaf {
+            key: key.clone(),
+            old_v: [0u8; 32],
+            new_v: u128le_to_h256(500000000000, 0),
+        }];
+        let proof = gen_proofs(&mut states, &leaves);
+        let transfer_in = Proof {
+            event_id: 2,
+            user_id: bob.clone(),
+            cmd: Command::TransferIn(1.into(), 500000000000.into()),
+            leaves,
+            maker_page_delta: 0,
+            maker_account_delta: 0,
+            merkle_proof: proof,
+            root: states.root().clone().into(),
+        };
+        assert_ok!(Verifier::verify(
+            RuntimeOrigin::signed(alice.clone()),
+            vec![reject_transfer_in, transfer_in]
+        ));
+        System::assert_has_event(RuntimeEvent::Verifier(crate::Event::TransferInExpired(
             alice.clone(),
+            ferdie.clone(),
+            1,
+            500000000000,
+        )));
+        let root: [u8; 32] = states.root().clone().into();
+        assert_eq!(Verifier::dominators(&alice).unwrap().merkle_root, root);
+        assert_eq!(Verifier::receipts(alice.clone(), bob.clone()), None);
+        assert_eq!(
+            Verifier::reserves(&(RESERVE_FOR_AUTHORIZING, bob.clone(), 1u32), &alice),
+            500000000000
         );
-        assert_eq!(t, 100000);
+        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
+        assert_eq!(
+            Verifier::reserves(
+                &(RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1u32),
+                &alice
+            ),
+            0
+        );
+        assert_eq!(Token::reserved_balance(&1, &ferdie), 0);
+        assert_eq!(Token::free_balance(&1, &ferdie), 10000000000000000000);
         assert_noop!(
-            Verifier::authorize(
+            Verifier::reclaim_authorizing(
                 RuntimeOrigin::signed(ferdie.clone()),
                 MultiAddress::Id(alice.clone()),
-                1,
-                1000000
             ),
-            Error::<Test>::ReceiptAlreadyExists
-        );
-        let t = Verifier::reserves(
-            (RESERVE_FOR_AUTHORIZING_STASH, ferdie.clone(), 1),
-            alice.clone(),
-        );
-        assert_eq!(t, 100000);
-    });
-}
-
-#[test]
-pub fn test_set_price_should_work() {
-    new_tester().execute_with(|| {
-        let alice: AccountId = AccountKeyring::Alice.into();
-        let bob: AccountId = AccountKeyring::Bob.into();
-        let ferdie: AccountId = AccountKeyring::Ferdie.into();
-        frame_system::Pallet::<Test>::set_block_number(15);
-        let usdt = XToken::NEP141(
-            br#"USDT"#.to_vec(),
-            br#"usdt.testnet"#.to_vec(),
-            Zero::zero(),
-            false,
-            6,
+            Error::<Test>::ReceiptNotExists
         );
-        assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
-        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
-        Indicator::set_price(1, 9_000_000, 3_000_000, 3333);
-        let r = Indicator::get_price(&1u32.into());
-        assert_eq!(r, 333333333333333333);
     });
 }
 
 #[test]
-pub fn test_revoke_should_work() {
+pub fn test_failed_callback_could_be_retried() {
     new_tester().execute_with(|| {
-        run_to_block(100);
         let alice: AccountId = AccountKeyring::Alice.into();
         let bob: AccountId = AccountKeyring::Bob.into();
         let ferdie: AccountId = AccountKeyring::Ferdie.into();
@@ -383,23 +1817,8 @@ pub fn test_revoke_should_work() {
             true,
             6,
         );
-
         assert_ok!(Token::issue(RawOrigin::Signed(TREASURY).into(), usdt,));
         assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
-        // assert_ok!(Token::issue(
-        //     RuntimeOrigin::signed(ferdie.clone()),
-        //     10000000000000000000,
-        //     br#"USDT"#.to_vec()
-        // ));
-        let token_info = Token::get_token_info(1);
-        assert!(token_info.is_some());
-        let token_info: XToken<u128> = token_info.unwrap();
-        match token_info {
-            XToken::NEP141(_, _, total, _, _) => {
-                assert_eq!(total, 10000000000000000000);
-            }
-            _ => {}
-        }
         assert_ok!(Verifier::register(
             RuntimeOrigin::signed(alice.clone()),
             b"cool".to_vec()
@@ -411,7 +1830,7 @@ pub fn test_revoke_should_work() {
         assert_ok!(Verifier::stake(
             RuntimeOrigin::signed(ferdie.clone()),
             MultiAddress::Id(alice.clone()),
-            800000000000
+            10000
         ));
         run_to_block(1000);
         assert_ok!(Verifier::authorize(
@@ -420,10 +1839,6 @@ pub fn test_revoke_should_work() {
             1,
             500000000000
         ));
-        assert_eq!(
-            Verifier::receipts(alice.clone(), ferdie.clone()),
-            Some(Receipt::Authorize(1, 500000000000u128.into(), 1000))
-        );
         use codec::Encode;
         let mut states = GlobalStates::default();
         let key = [&[0x00][..], &ferdie.encode()[..], &u32::to_le_bytes(1)[..]].concat();
@@ -446,42 +1861,20 @@ pub fn test_revoke_should_work() {
                 root: states.root().clone().into(),
             }]
         ));
-        assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
-
+        // ferdie doesn't have enough USDT to transfer
+        let callback =
+            crate::mock::RuntimeCall::TokenModule(pallet_fuso_token::Call::<Test>::transfer {
+                token: 1,
+                target: MultiAddress::Id(bob.clone()),
+                amount: 20000000000000000000,
+            });
         assert_ok!(Verifier::revoke_with_callback(
             RuntimeOrigin::signed(ferdie.clone()),
             MultiAddress::Id(alice.clone()),
             1,
             500000000000,
-            Box::new(crate::mock::RuntimeCall::Balances(pallet_balances::Call::<
-                Test,
-            >::transfer {
-                dest: MultiAddress::Id(bob.clone()),
-                value: 500000000000u128.into(),
-            })),
+            Box::new(callback.clone()),
         ));
-        assert_eq!(
-            Verifier::receipts(alice.clone(), ferdie.clone()),
-            Some(Receipt::RevokeWithCallback(
-                1,
-                500000000000u128.into(),
-                1000,
-                crate::mock::RuntimeCall::Balances(pallet_balances::Call::<Test>::transfer {
-                    dest: MultiAddress::Id(bob.clone()),
-                    value: 500000000000u128.into(),
-                })
-            ))
-        );
-        // let call = crate::mock::Call::Balances(pallet_balances::Call::<Test>::transfer {
-        //     dest: MultiAddress::Id(bob.clone()),
-        //     value: 500000000000u128.into(),
-        // });
-
-        // frame_support::dispatch::Dispatchable::dispatch(
-        //     call,
-        //     RawOrigin::Signed(ferdie.clone()).into(),
-        // )
-        // .unwrap();
         let leaves = vec![MerkleLeaf {
             key: key.clone(),
             new_v: [0u8; 32],
@@ -502,8 +1895,45 @@ pub fn test_revoke_should_work() {
             }]
         ));
         assert_eq!(Verifier::receipts(alice.clone(), ferdie.clone()), None);
-        run_to_block(1002);
-        assert_eq!(Balances::free_balance(bob.clone()), 500000000000);
+        let failed = Verifier::failed_callbacks(&ferdie, 0).unwrap();
+        assert_eq!(failed.callback, callback);
+        assert_eq!(failed.failed_at, 1000);
+        assert_noop!(
+            Verifier::retry_callback(
+                RuntimeOrigin::signed(ferdie.clone()),
+                0,
+                Weight::from_ref_time(0)
+            ),
+            Error::<Test>::CallbackWeightTooLow
+        );
+        assert_noop!(
+            Verifier::retry_callback(
+                RuntimeOrigin::signed(bob.clone()),
+                0,
+                Weight::from_ref_time(u64::MAX)
+            ),
+            Error::<Test>::CallbackNotExists
+        );
+        assert_noop!(
+            Verifier::retry_callback(
+                RuntimeOrigin::signed(ferdie.clone()),
+                0,
+                Weight::from_ref_time(u64::MAX)
+            ),
+            pallet_fuso_token::Error::<Test>::InsufficientBalance
+        );
+        assert_ok!(Token::do_mint(1, &ferdie, 10000000, None));
+        assert_ok!(Verifier::retry_callback(
+            RuntimeOrigin::signed(ferdie.clone()),
+            0,
+            Weight::from_ref_time(u64::MAX)
+        ));
+        assert_eq!(Verifier::failed_callbacks(&ferdie, 0), None);
+        assert_eq!(Token::free_balance(&1, &bob), 20000000000000000000);
+        assert_noop!(
+            Verifier::discard_callback(RuntimeOrigin::signed(ferdie.clone()), 0),
+            Error::<Test>::CallbackNotExists
+        );
     });
 }
 
@@ -37,22 +37,39 @@ pub trait WeightInfo {
 	fn revoke() -> Weight;
 	fn launch() -> Weight;
 	fn evict() -> Weight;
-	fn verify() -> Weight;
+	fn verify(p: u32, ) -> Weight;
 	fn stake() -> Weight;
 	fn unstake() -> Weight;
 	fn claim_shares() -> Weight;
+	fn claim_all_shares(c: u32, ) -> Weight;
 	fn update_setting() -> Weight;
 	fn set_beneficiary() -> Weight;
+	fn reclaim_authorizing() -> Weight;
+	fn retry_callback() -> Weight;
+	fn discard_callback() -> Weight;
+	fn redelegate() -> Weight;
+	fn rebond() -> Weight;
+	fn snapshot_dominator() -> Weight;
+	fn unlock_unbonding() -> Weight;
+	fn sweep_dust() -> Weight;
+	fn migrate_authorization() -> Weight;
+	fn set_pause() -> Weight;
+	fn rename() -> Weight;
+	fn set_pubkey() -> Weight;
+	fn set_commission() -> Weight;
+	fn set_payee() -> Weight;
+	fn set_auto_compound() -> Weight;
 }
 
 /// Weight functions for `pallet_fuso_verifier`.
 pub struct FusoWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for FusoWeight<T> {
-	// Storage: Verifier Dominators (r:2 w:1)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier DominatorByName (r:1 w:1)
 	fn register() -> Weight {
 		Weight::from_ref_time(17_000_000u64)
 			.saturating_add(T::DbWeight::get().reads(2u64))
-			.saturating_add(T::DbWeight::get().writes(1u64))
+			.saturating_add(T::DbWeight::get().writes(2u64))
 	}
 	// Storage: Verifier Dominators (r:1 w:1)
 	fn evict() -> Weight {
@@ -68,28 +85,76 @@ impl<T: frame_system::Config> WeightInfo for FusoWeight<T> {
 	}
 	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
 	// Storage: Verifier Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
 	fn stake() -> Weight {
-		Weight::from_ref_time(38_000_000u64)
-			.saturating_add(T::DbWeight::get().reads(4u64))
-			.saturating_add(T::DbWeight::get().writes(4u64))
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(14u64))
+			.saturating_add(T::DbWeight::get().writes(15u64))
 	}
 	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
-	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Unbondings (r:1 w:1)
 	fn unstake() -> Weight {
-		Weight::from_ref_time(43_000_000u64)
-			.saturating_add(T::DbWeight::get().reads(4u64))
-			.saturating_add(T::DbWeight::get().writes(4u64))
+		Weight::from_ref_time(70_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(17u64))
+			.saturating_add(T::DbWeight::get().writes(18u64))
 	}
-	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:1 w:1)
 	fn claim_shares() -> Weight {
-		Weight::from_ref_time(14_000_000u64)
-			.saturating_add(T::DbWeight::get().reads(2u64))
-			.saturating_add(T::DbWeight::get().writes(1u64))
+		Weight::from_ref_time(40_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(13u64))
+			.saturating_add(T::DbWeight::get().writes(13u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:1 w:0)
+	fn claim_all_shares(c: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000u64)
+			.saturating_add(Weight::from_ref_time(40_000_000u64).saturating_mul(c as u64))
+			.saturating_add(T::DbWeight::get().reads(1u64))
+			.saturating_add(T::DbWeight::get().reads((14u64).saturating_mul(c as u64)))
+			.saturating_add(T::DbWeight::get().writes((13u64).saturating_mul(c as u64)))
 	}
 	// Storage: Verifier Dominators (r:1 w:0)
 	// Storage: Verifier Receipts (r:1 w:1)
@@ -116,16 +181,26 @@ impl<T: frame_system::Config> WeightInfo for FusoWeight<T> {
 	// Storage: Token Balances (r:2 w:2)
 	// Storage: Reward Volumes (r:1 w:1)
 	// Storage: Reward Rewards (r:2 w:2)
-	fn verify() -> Weight {
-		Weight::from_ref_time(6_669_000_000u64)
-			.saturating_add(T::DbWeight::get().reads(15u64))
-			.saturating_add(T::DbWeight::get().writes(16u64))
+	// Storage: Verifier RootCheckpoints (r:1 w:1)
+	// Storage: Verifier AuthorizedTotals (r:1 w:1)
+	// Storage: Verifier Dusts (r:1 w:1)
+	// Storage: Verifier DominatorCommissions (r:1 w:0)
+	// Storage: Verifier PendingCommissions (r:1 w:0)
+	// Storage: Verifier DominatorSettings (r:1 w:0)
+	// Storage: Verifier ApprovedQuotes (r:1 w:0)
+	// Storage: Verifier PendingMigrations (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:1)
+	fn verify(p: u32, ) -> Weight {
+		Weight::from_ref_time(669_000_000u64)
+			.saturating_add(Weight::from_ref_time(60_000_000u64).saturating_mul(p as u64))
+			.saturating_add(T::DbWeight::get().reads((24u64).saturating_mul(p as u64)))
+			.saturating_add(T::DbWeight::get().writes((21u64).saturating_mul(p as u64)))
 	}
 
 	fn update_setting() -> Weight {
 		Weight::from_ref_time(569_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(1u64))
-			.saturating_add(RocksDbWeight::get().writes(1u64))
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
 
 	fn set_beneficiary() -> Weight {
@@ -133,15 +208,135 @@ impl<T: frame_system::Config> WeightInfo for FusoWeight<T> {
 			.saturating_add(RocksDbWeight::get().reads(1u64))
 			.saturating_add(RocksDbWeight::get().writes(1u64))
 	}
+	// Storage: Verifier Receipts (r:1 w:1)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Token Balances (r:1 w:1)
+	fn reclaim_authorizing() -> Weight {
+		Weight::from_ref_time(30_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(3u64))
+			.saturating_add(RocksDbWeight::get().writes(3u64))
+	}
+	// Storage: Verifier FailedCallbacks (r:1 w:1)
+	fn retry_callback() -> Weight {
+		Weight::from_ref_time(20_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(1u64))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier FailedCallbacks (r:1 w:1)
+	fn discard_callback() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(1u64))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier LastRedelegations (r:1 w:1)
+	// Storage: Verifier Dominators (r:2 w:2)
+	// Storage: Verifier Stakings (r:2 w:2)
+	// Storage: Verifier Reserves (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:2)
+	fn redelegate() -> Weight {
+		Weight::from_ref_time(80_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(8u64))
+			.saturating_add(T::DbWeight::get().writes(10u64))
+	}
+	// Storage: Verifier Unbondings (r:1 w:1)
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier Reserves (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
+	fn rebond() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(7u64))
+			.saturating_add(T::DbWeight::get().writes(8u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:1 w:0)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier SeasonRewards (r:0 w:1)
+	fn snapshot_dominator() -> Weight {
+		Weight::from_ref_time(18_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(5u64))
+			.saturating_add(RocksDbWeight::get().writes(4u64))
+	}
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Unbondings (r:0 w:1)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock_unbonding() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(3u64))
+			.saturating_add(RocksDbWeight::get().writes(4u64))
+	}
+	// Storage: Verifier Dusts (r:1 w:1)
+	// Storage: Token Balances (r:1 w:1)
+	fn sweep_dust() -> Weight {
+		Weight::from_ref_time(21_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Reserves (r:1 w:0)
+	// Storage: Verifier Dominators (r:2 w:0)
+	// Storage: Verifier Receipts (r:3 w:1)
+	// Storage: Verifier PendingMigrations (r:0 w:1)
+	fn migrate_authorization() -> Weight {
+		Weight::from_ref_time(34_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(6u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier DominatorPauses (r:0 w:1)
+	fn set_pause() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier DominatorByName (r:2 w:2)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn rename() -> Weight {
+		Weight::from_ref_time(32_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(5u64))
+			.saturating_add(RocksDbWeight::get().writes(5u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier X25519Keys (r:1 w:1)
+	fn set_pubkey() -> Weight {
+		Weight::from_ref_time(16_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(2u64))
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier PendingCommissions (r:2 w:1)
+	// Storage: Verifier DominatorCommissions (r:2 w:1)
+	fn set_commission() -> Weight {
+		Weight::from_ref_time(19_000_000u64)
+			.saturating_add(T::DbWeight::get().reads(5u64))
+			.saturating_add(T::DbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Payees (r:0 w:1)
+	fn set_payee() -> Weight {
+		Weight::from_ref_time(12_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier AutoCompounds (r:0 w:1)
+	fn set_auto_compound() -> Weight {
+		Weight::from_ref_time(12_000_000u64)
+			.saturating_add(T::DbWeight::get().writes(1u64))
+	}
 }
 
 impl WeightInfo for () {
 
-	// Storage: Verifier Dominators (r:2 w:1)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier DominatorByName (r:1 w:1)
 	fn register() -> Weight {
 		Weight::from_ref_time(17_000_000u64)
 			.saturating_add(RocksDbWeight::get().reads(2u64))
-			.saturating_add(RocksDbWeight::get().writes(1u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
 	// Storage: Verifier Dominators (r:1 w:1)
 	fn evict() -> Weight {
@@ -159,28 +354,76 @@ impl WeightInfo for () {
 
 	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
 	// Storage: Verifier Reserves (r:1 w:1)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
 	fn stake() -> Weight {
-		Weight::from_ref_time(38_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(4u64))
-			.saturating_add(RocksDbWeight::get().writes(4u64))
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(14u64))
+			.saturating_add(RocksDbWeight::get().writes(15u64))
 	}
 	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
-	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:2 w:2)
 	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Unbondings (r:1 w:1)
 	fn unstake() -> Weight {
-		Weight::from_ref_time(43_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(4u64))
-			.saturating_add(RocksDbWeight::get().writes(4u64))
+		Weight::from_ref_time(70_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(17u64))
+			.saturating_add(RocksDbWeight::get().writes(18u64))
 	}
-	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier Dominators (r:1 w:1)
 	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:1 w:1)
 	fn claim_shares() -> Weight {
-		Weight::from_ref_time(14_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(2u64))
-			.saturating_add(RocksDbWeight::get().writes(1u64))
+		Weight::from_ref_time(40_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(13u64))
+			.saturating_add(RocksDbWeight::get().writes(13u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:2)
+	// Storage: Verifier SeasonRewards (r:1 w:2)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:2 w:2)
+	// Storage: Verifier Payees (r:1 w:0)
+	// Storage: Verifier AutoCompounds (r:1 w:0)
+	// Storage: Token Balances (r:2 w:2)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:1 w:0)
+	fn claim_all_shares(c: u32, ) -> Weight {
+		Weight::from_ref_time(10_000_000u64)
+			.saturating_add(Weight::from_ref_time(40_000_000u64).saturating_mul(c as u64))
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().reads((14u64).saturating_mul(c as u64)))
+			.saturating_add(RocksDbWeight::get().writes((13u64).saturating_mul(c as u64)))
 	}
 	// Storage: Verifier Dominators (r:1 w:0)
 	// Storage: Verifier Receipts (r:1 w:1)
@@ -207,16 +450,26 @@ impl WeightInfo for () {
 	// Storage: Token Balances (r:2 w:2)
 	// Storage: Reward Volumes (r:1 w:1)
 	// Storage: Reward Rewards (r:2 w:2)
-	fn verify() -> Weight {
-		Weight::from_ref_time(6_669_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(15u64))
-			.saturating_add(RocksDbWeight::get().writes(16u64))
+	// Storage: Verifier RootCheckpoints (r:1 w:1)
+	// Storage: Verifier AuthorizedTotals (r:1 w:1)
+	// Storage: Verifier Dusts (r:1 w:1)
+	// Storage: Verifier DominatorCommissions (r:1 w:0)
+	// Storage: Verifier PendingCommissions (r:1 w:0)
+	// Storage: Verifier DominatorSettings (r:1 w:0)
+	// Storage: Verifier ApprovedQuotes (r:1 w:0)
+	// Storage: Verifier PendingMigrations (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:1)
+	fn verify(p: u32, ) -> Weight {
+		Weight::from_ref_time(669_000_000u64)
+			.saturating_add(Weight::from_ref_time(60_000_000u64).saturating_mul(p as u64))
+			.saturating_add(RocksDbWeight::get().reads((24u64).saturating_mul(p as u64)))
+			.saturating_add(RocksDbWeight::get().writes((21u64).saturating_mul(p as u64)))
 	}
 
 	fn update_setting() -> Weight {
 		Weight::from_ref_time(569_000_000u64)
-			.saturating_add(RocksDbWeight::get().reads(1u64))
-			.saturating_add(RocksDbWeight::get().writes(1u64))
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
 	}
 
 	fn set_beneficiary() -> Weight {
@@ -224,4 +477,123 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1u64))
 			.saturating_add(RocksDbWeight::get().writes(1u64))
 	}
+	// Storage: Verifier Receipts (r:1 w:1)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: Token Balances (r:1 w:1)
+	fn reclaim_authorizing() -> Weight {
+		Weight::from_ref_time(30_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(3u64))
+			.saturating_add(RocksDbWeight::get().writes(3u64))
+	}
+	// Storage: Verifier FailedCallbacks (r:1 w:1)
+	fn retry_callback() -> Weight {
+		Weight::from_ref_time(20_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier FailedCallbacks (r:1 w:1)
+	fn discard_callback() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier LastRedelegations (r:1 w:1)
+	// Storage: Verifier Dominators (r:2 w:2)
+	// Storage: Verifier Stakings (r:2 w:2)
+	// Storage: Verifier Reserves (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:2)
+	fn redelegate() -> Weight {
+		Weight::from_ref_time(80_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(8u64))
+			.saturating_add(RocksDbWeight::get().writes(10u64))
+	}
+	// Storage: Verifier Unbondings (r:1 w:1)
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: Verifier Stakings (r:1 w:1)
+	// Storage: Verifier Reserves (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Verifier StakedDominators (r:0 w:1)
+	fn rebond() -> Weight {
+		Weight::from_ref_time(60_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(7u64))
+			.saturating_add(RocksDbWeight::get().writes(8u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier StakingCheckpoints (r:1 w:1)
+	// Storage: Verifier Bonuses (r:1 w:1)
+	// Storage: Verifier StakingCounts (r:1 w:0)
+	// Storage: Verifier AccumulatedRewards (r:1 w:1)
+	// Storage: Verifier SeasonRewards (r:0 w:1)
+	fn snapshot_dominator() -> Weight {
+		Weight::from_ref_time(18_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(5u64))
+			.saturating_add(RocksDbWeight::get().writes(4u64))
+	}
+	// Storage: Verifier PendingUnstakings (r:1 w:1)
+	// Storage: Verifier Unbondings (r:0 w:1)
+	// Storage: Verifier Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn unlock_unbonding() -> Weight {
+		Weight::from_ref_time(25_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(3u64))
+			.saturating_add(RocksDbWeight::get().writes(4u64))
+	}
+	// Storage: Verifier Dusts (r:1 w:1)
+	// Storage: Token Balances (r:1 w:1)
+	fn sweep_dust() -> Weight {
+		Weight::from_ref_time(21_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Reserves (r:1 w:0)
+	// Storage: Verifier Dominators (r:2 w:0)
+	// Storage: Verifier Receipts (r:3 w:1)
+	// Storage: Verifier PendingMigrations (r:0 w:1)
+	fn migrate_authorization() -> Weight {
+		Weight::from_ref_time(34_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(6u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier DominatorPauses (r:0 w:1)
+	fn set_pause() -> Weight {
+		Weight::from_ref_time(15_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(1u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier DominatorByName (r:2 w:2)
+	// Storage: Verifier Dominators (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn rename() -> Weight {
+		Weight::from_ref_time(32_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(5u64))
+			.saturating_add(RocksDbWeight::get().writes(5u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier X25519Keys (r:1 w:1)
+	fn set_pubkey() -> Weight {
+		Weight::from_ref_time(16_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(2u64))
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier Dominators (r:1 w:0)
+	// Storage: Verifier PendingCommissions (r:2 w:1)
+	// Storage: Verifier DominatorCommissions (r:2 w:1)
+	fn set_commission() -> Weight {
+		Weight::from_ref_time(19_000_000u64)
+			.saturating_add(RocksDbWeight::get().reads(5u64))
+			.saturating_add(RocksDbWeight::get().writes(2u64))
+	}
+	// Storage: Verifier Payees (r:0 w:1)
+	fn set_payee() -> Weight {
+		Weight::from_ref_time(12_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
+	// Storage: Verifier AutoCompounds (r:0 w:1)
+	fn set_auto_compound() -> Weight {
+		Weight::from_ref_time(12_000_000u64)
+			.saturating_add(RocksDbWeight::get().writes(1u64))
+	}
 }
@@ -13,8 +13,13 @@
 // limitations under the License.
 
 use super::*;
+use crate::{
+    limiter::{BrokerMetrics, InflightGuard, Limiter, RateLimit},
+    relayer::{RelayError, Relayer},
+};
 use async_trait::async_trait;
 use codec::{Codec, Compact, Decode, Encode};
+use futures::StreamExt;
 use jsonrpsee::{
     core::{error::Error as RpcError, RpcResult},
     proc_macros::rpc,
@@ -22,7 +27,8 @@ use jsonrpsee::{
     types::SubscriptionResult,
     ws_server::SubscriptionSink,
 };
-use sc_client_api::{Backend, StorageProvider};
+use prometheus_endpoint::Registry;
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
 use sc_service::SpawnTaskHandle;
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
@@ -37,16 +43,28 @@ use sp_keystore::CryptoStore;
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
     generic::BlockId,
-    traits::{Block as BlockT, MaybeDisplay},
+    traits::{Block as BlockT, MaybeDisplay, TrailingZeroInput, UniqueSaturatedInto, Verify},
+    MultiSignature,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
 };
-use std::sync::Arc;
 
-// sha256
-type Signature = H256;
+type Signature = MultiSignature;
 type AccountId = AccountId32;
 
 pub const RELAYER_KEY_TYPE: KeyTypeId = KeyTypeId(*b"rely");
 
+const PRUNE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// the `PAUSE_TRADE` flag of `pallet-fuso-verifier`
+const PAUSE_TRADE: u8 = 0b10;
+
+/// the signed requests carry an `expire_at` in unix milliseconds, which must be in the future
+/// but no later than this
+const MAX_SIGNATURE_TTL: u64 = 60_000;
+
 #[derive(Clone, Encode, Decode, Eq, PartialEq)]
 pub struct DominatorSetting {
     pub beneficiary: Option<AccountId>,
@@ -54,6 +72,13 @@ pub struct DominatorSetting {
     pub rpc_endpoint: Vec<u8>,
 }
 
+#[derive(Clone, Encode, Decode, Eq, PartialEq)]
+pub struct X25519Key {
+    pub version: u32,
+    pub key: Vec<u8>,
+    pub activate_at: u32,
+}
+
 #[derive(Eq, PartialEq, Clone, TypeInfo, Encode, Decode, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TradingCommand {
@@ -64,6 +89,7 @@ pub enum TradingCommand {
         quote: u32,
         amount: Compact<u128>,
         price: Compact<u128>,
+        expire_at: u64,
         signature: Signature,
     },
     Bid {
@@ -73,48 +99,177 @@ pub enum TradingCommand {
         quote: u32,
         amount: Compact<u128>,
         price: Compact<u128>,
+        expire_at: u64,
         signature: Signature,
     },
     Cancel {
         order_id: String,
         account_id: AccountId,
+        expire_at: u64,
         signature: Signature,
     },
 }
 
+impl TradingCommand {
+    pub fn account_id(&self) -> &AccountId {
+        match self {
+            Self::Ask { account_id, .. }
+            | Self::Bid { account_id, .. }
+            | Self::Cancel { account_id, .. } => account_id,
+        }
+    }
+
+    pub fn expire_at(&self) -> u64 {
+        match self {
+            Self::Ask { expire_at, .. }
+            | Self::Bid { expire_at, .. }
+            | Self::Cancel { expire_at, .. } => *expire_at,
+        }
+    }
+
+    pub fn signature(&self) -> &Signature {
+        match self {
+            Self::Ask { signature, .. }
+            | Self::Bid { signature, .. }
+            | Self::Cancel { signature, .. } => signature,
+        }
+    }
+
+    /// the SCALE-encoded command without the signature, tagged by the command type
+    pub fn signing_payload(&self) -> Vec<u8> {
+        match self {
+            Self::Ask {
+                order_id,
+                account_id,
+                base,
+                quote,
+                amount,
+                price,
+                expire_at,
+                ..
+            } => (
+                0u8, order_id, account_id, base, quote, amount, price, expire_at,
+            )
+                .encode(),
+            Self::Bid {
+                order_id,
+                account_id,
+                base,
+                quote,
+                amount,
+                price,
+                expire_at,
+                ..
+            } => (
+                1u8, order_id, account_id, base, quote, amount, price, expire_at,
+            )
+                .encode(),
+            Self::Cancel {
+                order_id,
+                account_id,
+                expire_at,
+                ..
+            } => (2u8, order_id, account_id, expire_at).encode(),
+        }
+    }
+}
+
+/// a signature expired or expiring too far in the future can't be accepted
+pub fn is_fresh(expire_at: u64, now: u64) -> bool {
+    expire_at > now && expire_at <= now.saturating_add(MAX_SIGNATURE_TTL)
+}
+
+pub(crate) fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// verify the substrate signatures, or the ethereum personal-sign signatures of the accounts
+/// derived like `pallet-fuso-agent`
+pub fn verify_signature(
+    payload: &[u8],
+    signature: &Signature,
+    who: &AccountId,
+    agent_network: u16,
+) -> bool {
+    if signature.verify(payload, who) {
+        return true;
+    }
+    match signature {
+        MultiSignature::Ecdsa(signature) => {
+            let msg = [
+                &[0x19u8][..],
+                format!(
+                    "Ethereum Signed Message:\n{}{}",
+                    payload.len() * 2,
+                    hex::encode(payload)
+                )
+                .as_bytes(),
+            ]
+            .concat();
+            let digest = sp_core::keccak_256(&msg);
+            sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &digest)
+                .ok()
+                .map(|pubkey| {
+                    let address = sp_core::keccak_256(&pubkey)[12..].to_vec();
+                    let h = (b"-*-#fusotao#-*-", agent_network, address)
+                        .using_encoded(sp_core::blake2_256);
+                    AccountId::decode(&mut TrailingZeroInput::new(h.as_ref())).ok()
+                })
+                .flatten()
+                .map(|derived| &derived == who)
+                .unwrap_or(false)
+        }
+        _ => false,
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderEvent {
-    order_id: String,
-    account_id: AccountId,
-    base: u32,
-    quote: u32,
-    state: u8,
-    filled: Compact<u128>,
-    price: Compact<u128>,
-    update_at: u64,
+    pub order_id: String,
+    pub account_id: AccountId,
+    pub base: u32,
+    pub quote: u32,
+    pub state: u8,
+    pub filled: Compact<u128>,
+    pub price: Compact<u128>,
+    pub update_at: u64,
 }
 
 #[derive(Eq, PartialEq, Clone, Encode, Decode, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderState {
-    order_id: String,
-    direction: u8,
-    base: u32,
-    quote: u32,
-    state: u8,
-    unfilled: Compact<u128>,
-    total: Compact<u128>,
-    price: Compact<u128>,
-    filled_quote: Compact<u128>,
-    update_at: u64,
+    pub order_id: String,
+    pub direction: u8,
+    pub base: u32,
+    pub quote: u32,
+    pub state: u8,
+    pub unfilled: Compact<u128>,
+    pub total: Compact<u128>,
+    pub price: Compact<u128>,
+    pub filled_quote: Compact<u128>,
+    pub update_at: u64,
 }
 
 /// relayer + runtime_api
 #[rpc(client, server)]
 pub trait FusoBrokerApi {
+    /// the command is sealed to the x25519 key of the prover before relaying if `seal` is set
     #[method(name = "broker_placeOrder")]
-    async fn trade(&self, prover: AccountId, cmd: TradingCommand) -> RpcResult<String>;
+    async fn trade(
+        &self,
+        prover: AccountId,
+        cmd: TradingCommand,
+        seal: Option<bool>,
+    ) -> RpcResult<String>;
+
+    /// relay the `TradingCommand` sealed by the client without opening it, the signature inside
+    /// is verified by the prover
+    #[method(name = "broker_placeSealedOrder")]
+    async fn trade_sealed(&self, prover: AccountId, sealed: Bytes) -> RpcResult<String>;
 
     #[method(name = "broker_queryOrders")]
     async fn query_orders(
@@ -122,18 +277,22 @@ pub trait FusoBrokerApi {
         prover: AccountId,
         account_id: AccountId,
         orders: Vec<(u32, u32, String)>,
+        expire_at: u64,
         signature: Signature,
-    ) -> RpcResult<Vec<Bytes>>;
+    ) -> RpcResult<Vec<OrderState>>;
 
+    /// the order events are pushed from the prover, resubscribing if the prover drops it until
+    /// the signature expires, after which the subscription is closed with error 93110
     #[subscription(
         name = "broker_subscribeOrderEvents",
         unsubscribe = "broker_unsubscribeOrderEvents",
-        item = Bytes,
+        item = OrderEvent,
     )]
     fn subscribe_order_events(
         &self,
         prover: AccountId,
         account_id: AccountId,
+        expire_at: u64,
         signature: Signature,
     );
 }
@@ -143,8 +302,12 @@ pub struct FusoBroker<C, B, S> {
     client: Arc<C>,
     task_handle: SpawnTaskHandle,
     keystore: Arc<dyn CryptoStore>,
+    relayer: Relayer,
+    endpoints: Arc<RwLock<HashMap<AccountId, Vec<Vec<u8>>>>>,
+    limiter: Limiter,
+    metrics: Option<BrokerMetrics>,
+    agent_network: u16,
     _marker: std::marker::PhantomData<(B, S)>,
-    // TODO maintain a connection and the map prover -> rpc_endpoint
 }
 
 impl<Client, Block, Storage> FusoBroker<Client, Block, Storage>
@@ -154,6 +317,7 @@ where
         + ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + StorageProvider<Block, Storage>
+        + BlockchainEvents<Block>
         + 'static,
     Storage: Backend<Block> + 'static,
     Block: BlockT + 'static,
@@ -162,44 +326,321 @@ where
         client: Arc<Client>,
         task_handle: SpawnTaskHandle,
         keystore: Arc<dyn CryptoStore>,
+        agent_network: u16,
+        rate_limit: RateLimit,
+        prometheus_registry: Option<&Registry>,
     ) -> Self {
-        task_handle.spawn("broker-relayer", "fusotao", async {
-            println!("===> TODO connect to prover");
+        let relayer = Relayer::default();
+        let limiter = Limiter::new(rate_limit);
+        let pruning = (relayer.clone(), limiter.clone());
+        task_handle.spawn("broker-relayer", "fusotao", async move {
+            let (relayer, limiter) = pruning;
+            loop {
+                tokio::time::sleep(PRUNE_INTERVAL).await;
+                relayer.prune().await;
+                limiter.prune();
+            }
+        });
+        let metrics = prometheus_registry.and_then(|r| BrokerMetrics::register(r).ok());
+        let endpoints = Arc::new(RwLock::new(HashMap::new()));
+        let watching = (client.clone(), endpoints.clone(), relayer.clone());
+        task_handle.spawn("broker-endpoints", "fusotao", async move {
+            let (client, endpoints, relayer) = watching;
+            let mut finalized = client.finality_notification_stream();
+            while let Some(notification) = finalized.next().await {
+                let blocks = notification
+                    .tree_route
+                    .iter()
+                    .cloned()
+                    .chain(std::iter::once(notification.hash))
+                    .collect::<Vec<_>>();
+                for prover in Self::refresh_endpoints(&client, &endpoints, &blocks) {
+                    relayer.disconnect(&prover).await;
+                }
+            }
         });
         Self {
             client,
             task_handle,
             keystore,
+            relayer,
+            endpoints,
+            limiter,
+            metrics,
+            agent_network,
             _marker: Default::default(),
         }
     }
 
-    fn get_prover_rpc(&self, prover: AccountId) -> Option<Vec<u8>> {
-        let key = super::blake2_128concat_storage_key(b"Verifier", b"DominatorSettings", prover);
-        self.client
-            .storage(&BlockId::Hash(self.client.info().best_hash), &key)
+    /// the `rpc_endpoint` of the prover is the primary endpoint optionally followed by the
+    /// secondaries, separated by comma, which is validated by the runtime
+    fn read_prover_rpc(client: &Client, at: Block::Hash, prover: &AccountId) -> Vec<Vec<u8>> {
+        let key =
+            super::blake2_128concat_storage_key(b"Verifier", b"DominatorSettings", prover.clone());
+        client
+            .storage(&BlockId::Hash(at), &key)
             .ok()
             .flatten()
-            .map(|v| {
-                DominatorSetting::decode(&mut v.0.as_slice())
-                    .ok()
-                    .map(|s| s.rpc_endpoint)
+            .map(|v| DominatorSetting::decode(&mut v.0.as_slice()).ok())
+            .flatten()
+            .map(|s| {
+                String::from_utf8_lossy(&s.rpc_endpoint)
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|endpoint| !endpoint.is_empty())
+                    .map(|endpoint| endpoint.as_bytes().to_vec())
+                    .collect()
             })
+            .unwrap_or_default()
+    }
+
+    /// the provers updating their endpoints in the block, cleared by the runtime every block
+    fn read_endpoint_updates(client: &Client, at: Block::Hash) -> Vec<AccountId> {
+        let key = super::storage_value_key(b"Verifier", b"RpcEndpointUpdates");
+        client
+            .storage(&BlockId::Hash(at), &key)
+            .ok()
             .flatten()
+            .map(|v| Vec::<AccountId>::decode(&mut v.0.as_slice()).ok())
+            .flatten()
+            .unwrap_or_default()
+    }
+
+    /// re-read the endpoints of the cached provers updated in the newly finalized blocks,
+    /// return the provers whose endpoints have been changed
+    fn refresh_endpoints(
+        client: &Client,
+        endpoints: &RwLock<HashMap<AccountId, Vec<Vec<u8>>>>,
+        finalized: &[Block::Hash],
+    ) -> Vec<AccountId> {
+        let mut updated = finalized
+            .iter()
+            .flat_map(|hash| Self::read_endpoint_updates(client, *hash))
+            .collect::<Vec<_>>();
+        updated.sort();
+        updated.dedup();
+        let latest_block = match finalized.last() {
+            Some(hash) => *hash,
+            None => return vec![],
+        };
+        let mut endpoints = endpoints.write().expect("endpoints lock poisoned");
+        updated
+            .into_iter()
+            .filter_map(|prover| {
+                let latest = Self::read_prover_rpc(client, latest_block, &prover);
+                (endpoints.get(&prover)? != &latest).then(|| {
+                    endpoints.insert(prover.clone(), latest);
+                    prover
+                })
+            })
+            .collect()
+    }
+
+    /// the cached endpoints of the prover, read from the finalized block on missing
+    fn get_prover_rpc(&self, prover: AccountId) -> Vec<Vec<u8>> {
+        if let Some(cached) = self
+            .endpoints
+            .read()
+            .expect("endpoints lock poisoned")
+            .get(&prover)
+        {
+            return cached.clone();
+        }
+        let latest =
+            Self::read_prover_rpc(&self.client, self.client.info().finalized_hash, &prover);
+        // don't let the unknown accounts fill up the cache
+        if !latest.is_empty() {
+            self.endpoints
+                .write()
+                .expect("endpoints lock poisoned")
+                .insert(prover, latest.clone());
+        }
+        latest
+    }
+
+    /// the current x25519 key of the prover at the best block followed by the rotating one
+    fn get_prover_keys(&self, prover: AccountId) -> Vec<X25519Key> {
+        let key = super::blake2_128concat_storage_key(b"Verifier", b"X25519Keys", prover);
+        let info = self.client.info();
+        let best: u32 = info.best_number.unique_saturated_into();
+        let keys = self
+            .client
+            .storage(&BlockId::Hash(info.best_hash), &key)
+            .ok()
+            .flatten()
+            .map(|v| Vec::<X25519Key>::decode(&mut v.0.as_slice()).ok())
+            .flatten()
+            .unwrap_or_default();
+        match keys.iter().rposition(|k| k.activate_at <= best) {
+            Some(current) => keys[current..].to_vec(),
+            None => vec![],
+        }
+    }
+
+    fn seal_command(&self, prover: AccountId, cmd: &TradingCommand) -> RpcResult<Vec<u8>> {
+        let keys = self.get_prover_keys(prover);
+        let current = keys
+            .first()
+            .ok_or(RpcError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93102i32).code(),
+                "The prover hasn't published its x25519 key.",
+                None::<()>,
+            ))))?;
+        sealing::seal(current.version, &current.key, &cmd.encode()).map_err(|e| {
+            RpcError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93103i32).code(),
+                "Unable to seal the command.",
+                Some(format!("{:?}", e)),
+            )))
+        })
+    }
+
+    fn require_prover_rpc(&self, prover: AccountId) -> Result<Vec<Vec<u8>>, CallError> {
+        Some(self.get_prover_rpc(prover))
+            .filter(|endpoints| !endpoints.is_empty())
+            .ok_or(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93104i32).code(),
+                "The prover hasn't set its rpc endpoint.",
+                None::<()>,
+            )))
+    }
+
+    /// the trading commands are refused while the prover or all provers are paused
+    fn require_trading(&self, prover: &AccountId) -> Result<(), CallError> {
+        let at = BlockId::Hash(self.client.info().best_hash);
+        let read = |key: StorageKey| -> u8 {
+            self.client
+                .storage(&at, &key)
+                .ok()
+                .flatten()
+                .map(|v| u8::decode(&mut v.0.as_slice()).ok())
+                .flatten()
+                .unwrap_or_default()
+        };
+        let flags = read(super::storage_value_key(b"Verifier", b"GlobalPause"))
+            | read(super::blake2_128concat_storage_key(
+                b"Verifier",
+                b"DominatorPauses",
+                prover.clone(),
+            ));
+        if flags & PAUSE_TRADE == 0 {
+            Ok(())
+        } else {
+            Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93111i32).code(),
+                "Trading with the prover is paused.",
+                None::<()>,
+            )))
+        }
+    }
+
+    /// the `expire_at` must have been signed within the `payload`
+    fn require_signature(
+        &self,
+        payload: &[u8],
+        signature: &Signature,
+        who: &AccountId,
+        expire_at: u64,
+    ) -> Result<(), CallError> {
+        if !is_fresh(expire_at, now_millis()) {
+            return Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93110i32).code(),
+                "The signature has expired.",
+                None::<()>,
+            )));
+        }
+        if verify_signature(payload, signature, who, self.agent_network) {
+            Ok(())
+        } else {
+            Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93107i32).code(),
+                "Invalid signature.",
+                None::<()>,
+            )))
+        }
+    }
+
+    fn throttled(&self, limit: &str, message: &str, code: i32) -> CallError {
+        if let Some(metrics) = &self.metrics {
+            metrics.throttled.with_label_values(&[limit]).inc();
+        }
+        CallError::Custom(ErrorObject::owned(
+            ErrorCode::ServerError(code).code(),
+            message,
+            None::<()>,
+        ))
+    }
+
+    fn require_quota(&self, who: &AccountId) -> Result<(), CallError> {
+        if self.limiter.acquire(who) {
+            Ok(())
+        } else {
+            Err(self.throttled("account", "Too many requests, please slow down.", 93108))
+        }
+    }
+
+    /// the sender of a sealed command is unknown, so a dedicated quota is kept per prover
+    fn require_sealed_quota(&self, prover: &AccountId) -> Result<(), CallError> {
+        if self.limiter.acquire_sealed(prover) {
+            Ok(())
+        } else {
+            Err(self.throttled(
+                "sealed",
+                "Too many sealed commands, please slow down.",
+                93108,
+            ))
+        }
+    }
+
+    fn require_slot(&self, prover: &AccountId) -> Result<InflightGuard, CallError> {
+        self.limiter.enter(prover).ok_or_else(|| {
+            self.throttled("prover", "The prover is busy, please retry later.", 93109)
+        })
+    }
+
+    fn observe<T>(&self, relayed: &Result<T, RelayError>) {
+        if let Some(metrics) = &self.metrics {
+            match relayed {
+                Ok(_) => metrics.relayed.inc(),
+                Err(_) => metrics.relay_failures.inc(),
+            }
+        }
     }
 
     /// the keystore is very unconvenient to use, be careful
-    async fn sign_request(&self, payload: &[u8]) -> Result<Vec<u8>, sp_keystore::Error> {
-        let key = CryptoStore::sr25519_public_keys(&*self.keystore, RELAYER_KEY_TYPE)
+    async fn sign_request(
+        &self,
+        payload: &[u8],
+    ) -> Result<(AccountId, Vec<u8>), sp_keystore::Error> {
+        let public = CryptoStore::sr25519_public_keys(&*self.keystore, RELAYER_KEY_TYPE)
             .await
-            .iter()
-            .map(|k| CryptoTypePublicPair(Sr25519Id, k.0.to_vec()))
             .last()
+            .cloned()
             .ok_or(sp_keystore::Error::Unavailable)?;
-        CryptoStore::sign_with(&*self.keystore, RELAYER_KEY_TYPE, &key, payload)
+        let key = CryptoTypePublicPair(Sr25519Id, public.0.to_vec());
+        let signature = CryptoStore::sign_with(&*self.keystore, RELAYER_KEY_TYPE, &key, payload)
             .await
             .transpose()
-            .ok_or(sp_keystore::Error::Unavailable)?
+            .ok_or(sp_keystore::Error::Unavailable)??;
+        Ok((AccountId::new(public.0), signature))
+    }
+
+    async fn relay(&self, prover: AccountId, payload: Vec<u8>) -> RpcResult<String> {
+        let endpoints = self.require_prover_rpc(prover.clone())?;
+        let _slot = self.require_slot(&prover)?;
+        let (relayer, signature) = self.sign_request(&payload).await.map_err(|e| {
+            RpcError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(93101i32).code(),
+                "The broker hasn't register its signing key, please switch to another node.",
+                Some(format!("{:?}", e)),
+            )))
+        })?;
+        let relayed = self
+            .relayer
+            .trade(&prover, &endpoints, payload, relayer, signature)
+            .await;
+        self.observe(&relayed);
+        relayed.map_err(|e| relay_error(e).into())
     }
 }
 
@@ -211,21 +652,46 @@ where
         + ProvideRuntimeApi<Block>
         + HeaderBackend<Block>
         + StorageProvider<Block, Storage>
+        + BlockchainEvents<Block>
         + 'static,
     Storage: Backend<Block> + 'static,
     Block: BlockT + 'static,
 {
-    async fn trade(&self, prover: AccountId, cmd: TradingCommand) -> RpcResult<String> {
-        let payload = cmd.encode();
-        let v = self.sign_request(&payload).await.map_err(|e| {
-            RpcError::Call(CallError::Custom(ErrorObject::owned(
-                ErrorCode::ServerError(93101i32).code(),
-                "The broker hasn't register its signing key, please switch to another node.",
-                Some(format!("{:?}", e)),
-            )))
-        })?;
-        // TODO RELAY
-        Ok("Ni4qf".to_string())
+    async fn trade(
+        &self,
+        prover: AccountId,
+        cmd: TradingCommand,
+        seal: Option<bool>,
+    ) -> RpcResult<String> {
+        self.require_trading(&prover)?;
+        self.require_signature(
+            &cmd.signing_payload(),
+            cmd.signature(),
+            cmd.account_id(),
+            cmd.expire_at(),
+        )?;
+        self.require_quota(cmd.account_id())?;
+        let payload = if seal.unwrap_or(false) {
+            self.seal_command(prover.clone(), &cmd)?
+        } else {
+            cmd.encode()
+        };
+        self.relay(prover, payload).await
+    }
+
+    async fn trade_sealed(&self, prover: AccountId, sealed: Bytes) -> RpcResult<String> {
+        self.require_trading(&prover)?;
+        self.require_sealed_quota(&prover)?;
+        let version = sealing::key_version(&sealed);
+        let keys = self.get_prover_keys(prover);
+        if !keys.iter().any(|k| Some(k.version) == version) {
+            return Err(RpcError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InvalidParams.code(),
+                "The command isn't sealed to a valid x25519 key of the prover.",
+                None::<()>,
+            ))));
+        }
+        self.relay(prover, sealed.0).await
     }
 
     async fn query_orders(
@@ -233,11 +699,31 @@ where
         prover: AccountId,
         account_id: AccountId,
         orders: Vec<(u32, u32, String)>,
+        expire_at: u64,
         signature: Signature,
-    ) -> RpcResult<Vec<Bytes>> {
-        let endpoint = self.get_prover_rpc(prover);
-        println!("request to {:?}", endpoint);
-        Ok(vec![])
+    ) -> RpcResult<Vec<OrderState>> {
+        self.require_signature(
+            &(&prover, &account_id, &orders, expire_at).encode(),
+            &signature,
+            &account_id,
+            expire_at,
+        )?;
+        self.require_quota(&account_id)?;
+        let endpoints = self.require_prover_rpc(prover.clone())?;
+        let _slot = self.require_slot(&prover)?;
+        let relayed = self
+            .relayer
+            .query_orders(
+                &prover,
+                &endpoints,
+                account_id,
+                orders,
+                expire_at,
+                Bytes(signature.encode()),
+            )
+            .await;
+        self.observe(&relayed);
+        relayed.map_err(|e| relay_error(e).into())
     }
 
     fn subscribe_order_events(
@@ -245,8 +731,135 @@ where
         mut sink: SubscriptionSink,
         prover: AccountId,
         account_id: AccountId,
+        expire_at: u64,
         signature: Signature,
     ) -> SubscriptionResult {
+        if let Err(e) = self.require_signature(
+            &(&prover, &account_id, expire_at).encode(),
+            &signature,
+            &account_id,
+            expire_at,
+        ) {
+            let _ = sink.reject(e);
+            return Ok(());
+        }
+        let endpoints = match self.require_prover_rpc(prover.clone()) {
+            Ok(endpoints) => endpoints,
+            Err(e) => {
+                let _ = sink.reject(e);
+                return Ok(());
+            }
+        };
+        let relayer = self.relayer.clone();
+        let signature = Bytes(signature.encode());
+        self.task_handle
+            .spawn("broker-subscription", "fusotao", async move {
+                let upstream = match relayer
+                    .subscribe_order_events(
+                        &prover,
+                        &endpoints,
+                        account_id.clone(),
+                        expire_at,
+                        signature.clone(),
+                    )
+                    .await
+                {
+                    Ok(upstream) => upstream,
+                    Err(e) => {
+                        let _ = sink.reject(relay_error(e));
+                        return;
+                    }
+                };
+                if sink.accept().is_err() {
+                    return;
+                }
+                relayer
+                    .pipe_order_events(
+                        sink, upstream, &prover, &endpoints, account_id, expire_at, signature,
+                    )
+                    .await;
+            });
         Ok(())
     }
 }
+
+fn relay_error(e: RelayError) -> CallError {
+    match e {
+        RelayError::Rejected(reason) => CallError::Custom(ErrorObject::owned(
+            ErrorCode::ServerError(93106i32).code(),
+            "The prover rejected the request.",
+            Some(reason),
+        )),
+        e => CallError::Custom(ErrorObject::owned(
+            ErrorCode::ServerError(93105i32).code(),
+            "Unable to connect to the prover, please try again later.",
+            Some(format!("{:?}", e)),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_core::{ecdsa, Pair};
+    use sp_keyring::AccountKeyring;
+
+    #[test]
+    fn verify_signature_should_work() {
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let cmd = TradingCommand::Cancel {
+            order_id: "7".to_string(),
+            account_id: alice.clone(),
+            expire_at: 1_000,
+            signature: AccountKeyring::Alice
+                .sign(&(2u8, "7".to_string(), alice.clone(), 1_000u64).encode())
+                .into(),
+        };
+        assert!(verify_signature(
+            &cmd.signing_payload(),
+            cmd.signature(),
+            cmd.account_id(),
+            1
+        ));
+        assert!(!verify_signature(
+            &cmd.signing_payload(),
+            cmd.signature(),
+            &AccountKeyring::Bob.into(),
+            1
+        ));
+
+        let pair = ecdsa::Pair::from_seed(&[7u8; 32]);
+        let payload = cmd.signing_payload();
+        let msg = [
+            &[0x19u8][..],
+            format!(
+                "Ethereum Signed Message:\n{}{}",
+                payload.len() * 2,
+                hex::encode(&payload)
+            )
+            .as_bytes(),
+        ]
+        .concat();
+        let signature = pair.sign_prehashed(&sp_core::keccak_256(&msg));
+        let pubkey =
+            sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &sp_core::keccak_256(&msg))
+                .unwrap();
+        let address = sp_core::keccak_256(&pubkey)[12..].to_vec();
+        let h = (b"-*-#fusotao#-*-", 1u16, address).using_encoded(sp_core::blake2_256);
+        let agent = AccountId::new(h);
+        let signature: Signature = signature.into();
+        assert!(verify_signature(&payload, &signature, &agent, 1));
+        assert!(!verify_signature(&payload, &signature, &agent, 0));
+        assert!(!verify_signature(&payload, &signature, &alice, 1));
+    }
+
+    #[test]
+    fn signature_freshness_should_be_checked() {
+        assert!(is_fresh(1_001, 1_000));
+        assert!(is_fresh(1_000 + MAX_SIGNATURE_TTL, 1_000));
+        assert!(!is_fresh(1_000, 1_000));
+        assert!(!is_fresh(999, 1_000));
+        assert!(!is_fresh(1_001 + MAX_SIGNATURE_TTL, 1_000));
+        assert!(!is_fresh(u64::MAX, u64::MAX));
+    }
+}
@@ -14,11 +14,16 @@
 
 #![feature(result_flattening)]
 pub mod broker;
+pub mod limiter;
 pub mod prover;
+pub mod relayer;
+pub mod sealing;
 pub mod token;
 
 pub use broker::{FusoBroker, FusoBrokerApiServer};
+pub use limiter::RateLimit;
 pub use prover::{FusoVerifier, FusoVerifierApiServer};
+pub use token::{FusoToken, TokenApiServer};
 
 pub fn blake2_128concat_storage_key<K: codec::Encode>(
     module: &[u8],
@@ -37,3 +42,9 @@ pub fn blake2_128concat_storage_key<K: codec::Encode>(
     bytes.extend(v);
     sp_core::storage::StorageKey(bytes)
 }
+
+pub fn storage_value_key(module: &[u8], storage: &[u8]) -> sp_core::storage::StorageKey {
+    let mut bytes = sp_core::twox_128(module).to_vec();
+    bytes.extend(&sp_core::twox_128(storage)[..]);
+    sp_core::storage::StorageKey(bytes)
+}
@@ -0,0 +1,256 @@
+// Copyright 2021-2023 UINB Technologies Pte. Ltd.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use prometheus_endpoint::{register, Counter, CounterVec, Opts, PrometheusError, Registry, U64};
+use sp_core::crypto::AccountId32;
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+type AccountId = AccountId32;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+    /// the requests replenished per second for each account
+    pub account_rate: u32,
+    /// the requests an idle account could send at once
+    pub account_burst: u32,
+    /// the sealed commands replenished per second for each prover, shared by all the senders
+    pub sealed_rate: u32,
+    /// the sealed commands an idle prover could be sent at once
+    pub sealed_burst: u32,
+    /// the relays waiting for the response of a prover
+    pub max_inflight_per_prover: u32,
+}
+
+impl Default for RateLimit {
+    fn default() -> Self {
+        Self {
+            account_rate: 5,
+            account_burst: 20,
+            sealed_rate: 50,
+            sealed_burst: 200,
+            max_inflight_per_prover: 64,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+#[derive(Clone)]
+struct Buckets<K> {
+    rate: f64,
+    burst: f64,
+    buckets: Arc<Mutex<HashMap<K, Bucket>>>,
+}
+
+impl<K: Clone + Eq + Hash> Buckets<K> {
+    fn new(rate: u32, burst: u32) -> Self {
+        Self {
+            rate: rate as f64,
+            burst: burst as f64,
+            buckets: Default::default(),
+        }
+    }
+
+    fn acquire_at(&self, key: &K, now: Instant) -> bool {
+        let mut buckets = self.buckets.lock().expect("buckets lock poisoned");
+        let bucket = buckets.entry(key.clone()).or_insert(Bucket {
+            tokens: self.burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// the refilled buckets are equivalent to the missing ones
+    fn prune_at(&self, now: Instant) {
+        let (rate, burst) = (self.rate, self.burst);
+        self.buckets
+            .lock()
+            .expect("buckets lock poisoned")
+            .retain(|_, b| {
+                b.tokens + now.saturating_duration_since(b.updated).as_secs_f64() * rate < burst
+            });
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.buckets.lock().unwrap().len()
+    }
+}
+
+/// token buckets per account and of the sealed commands per prover, and the in-flight relays
+/// per prover
+#[derive(Clone)]
+pub struct Limiter {
+    max_inflight_per_prover: u32,
+    accounts: Buckets<AccountId>,
+    sealed: Buckets<AccountId>,
+    inflight: Arc<Mutex<HashMap<AccountId, u32>>>,
+}
+
+impl Limiter {
+    pub fn new(config: RateLimit) -> Self {
+        Self {
+            max_inflight_per_prover: config.max_inflight_per_prover,
+            accounts: Buckets::new(config.account_rate, config.account_burst),
+            sealed: Buckets::new(config.sealed_rate, config.sealed_burst),
+            inflight: Default::default(),
+        }
+    }
+
+    pub fn acquire(&self, who: &AccountId) -> bool {
+        self.accounts.acquire_at(who, Instant::now())
+    }
+
+    pub fn acquire_sealed(&self, prover: &AccountId) -> bool {
+        self.sealed.acquire_at(prover, Instant::now())
+    }
+
+    /// occupy a relay slot of the prover until the guard is dropped
+    pub fn enter(&self, prover: &AccountId) -> Option<InflightGuard> {
+        let mut inflight = self.inflight.lock().expect("inflight lock poisoned");
+        let count = inflight.entry(prover.clone()).or_default();
+        if *count >= self.max_inflight_per_prover {
+            return None;
+        }
+        *count += 1;
+        Some(InflightGuard {
+            prover: prover.clone(),
+            inflight: self.inflight.clone(),
+        })
+    }
+
+    pub fn prune(&self) {
+        let now = Instant::now();
+        self.accounts.prune_at(now);
+        self.sealed.prune_at(now);
+    }
+}
+
+pub struct InflightGuard {
+    prover: AccountId,
+    inflight: Arc<Mutex<HashMap<AccountId, u32>>>,
+}
+
+impl Drop for InflightGuard {
+    fn drop(&mut self) {
+        let mut inflight = self.inflight.lock().expect("inflight lock poisoned");
+        if let Some(count) = inflight.get_mut(&self.prover) {
+            *count = count.saturating_sub(1);
+            if *count == 0 {
+                inflight.remove(&self.prover);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct BrokerMetrics {
+    pub relayed: Counter<U64>,
+    pub relay_failures: Counter<U64>,
+    pub throttled: CounterVec<U64>,
+}
+
+impl BrokerMetrics {
+    pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+        Ok(Self {
+            relayed: register(
+                Counter::new(
+                    "fusotao_broker_relayed_total",
+                    "Number of requests relayed to the provers",
+                )?,
+                registry,
+            )?,
+            relay_failures: register(
+                Counter::new(
+                    "fusotao_broker_relay_failures_total",
+                    "Number of relays failed or rejected by the provers",
+                )?,
+                registry,
+            )?,
+            throttled: register(
+                CounterVec::new(
+                    Opts::new(
+                        "fusotao_broker_throttled_total",
+                        "Number of requests refused by the rate limits",
+                    ),
+                    &["limit"],
+                )?,
+                registry,
+            )?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_keyring::AccountKeyring;
+    use std::time::Duration;
+
+    #[test]
+    fn token_bucket_should_work() {
+        let limiter = Limiter::new(RateLimit {
+            account_rate: 2,
+            account_burst: 3,
+            sealed_rate: 1,
+            sealed_burst: 1,
+            max_inflight_per_prover: 1,
+        });
+        let alice: AccountId = AccountKeyring::Alice.into();
+        let bob: AccountId = AccountKeyring::Bob.into();
+        let now = Instant::now();
+        let accounts = &limiter.accounts;
+        assert!(accounts.acquire_at(&alice, now));
+        assert!(accounts.acquire_at(&alice, now));
+        assert!(accounts.acquire_at(&alice, now));
+        assert!(!accounts.acquire_at(&alice, now));
+        assert!(accounts.acquire_at(&bob, now));
+        assert!(accounts.acquire_at(&alice, now + Duration::from_millis(500)));
+        assert!(!accounts.acquire_at(&alice, now + Duration::from_millis(500)));
+        accounts.prune_at(now + Duration::from_millis(500));
+        assert_eq!(accounts.len(), 1);
+        accounts.prune_at(now + Duration::from_secs(2));
+        assert_eq!(accounts.len(), 0);
+
+        // the sealed commands don't share the quota of the prover account
+        assert!(limiter.sealed.acquire_at(&bob, now));
+        assert!(!limiter.sealed.acquire_at(&bob, now));
+        assert!(limiter.accounts.acquire_at(&bob, now));
+
+        let guard = limiter.enter(&alice);
+        assert!(guard.is_some());
+        assert!(limiter.enter(&alice).is_none());
+        assert!(limiter.enter(&bob).is_some());
+        drop(guard);
+        assert!(limiter.enter(&alice).is_some());
+        assert!(limiter.inflight.lock().unwrap().is_empty());
+    }
+}
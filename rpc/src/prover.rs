@@ -20,8 +20,10 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
     generic::BlockId,
@@ -29,6 +31,15 @@ use sp_runtime::{
 };
 use std::sync::Arc;
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReservesSnapshot {
+    pub totals: Vec<(u32, NumberOrHex)>,
+    pub root: H256,
+    pub event_id: u64,
+    pub block_number: u32,
+}
+
 #[rpc(client, server)]
 pub trait FusoVerifierApi<BlockHash, AccountId, Balance> {
     #[method(name = "verifier_currentSeasonOfDominator")]
@@ -45,6 +56,41 @@ pub trait FusoVerifierApi<BlockHash, AccountId, Balance> {
         who: AccountId,
         at: Option<BlockHash>,
     ) -> RpcResult<NumberOrHex>;
+
+    #[method(name = "verifier_unbondingChunksOf")]
+    fn unbonding_chunks_of(
+        &self,
+        staker: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u32, NumberOrHex)>>;
+
+    #[method(name = "verifier_rootCheckpointsOf")]
+    fn root_checkpoints_of(
+        &self,
+        dominator: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u64, u32, H256)>>;
+
+    #[method(name = "verifier_proofOfReservesOf")]
+    fn proof_of_reserves_of(
+        &self,
+        dominator: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ReservesSnapshot>;
+
+    #[method(name = "verifier_dominatorOfName")]
+    fn dominator_of_name(
+        &self,
+        name: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AccountId>>;
+
+    #[method(name = "verifier_x25519KeysOf")]
+    fn x25519_keys_of(
+        &self,
+        dominator: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<(u32, Bytes, u32)>>;
 }
 
 pub struct FusoVerifier<C, B> {
@@ -61,6 +107,35 @@ impl<C, B> FusoVerifier<C, B> {
     }
 }
 
+impl<C, Block, AccountId, Balance> FusoVerifier<C, (Block, AccountId, Balance)>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: FusoVerifierRuntimeApi<Block, AccountId, Balance>,
+    Block: BlockT,
+    AccountId: Codec + MaybeDisplay,
+    Balance: Codec + MaybeDisplay,
+{
+    /// the older runtimes don't implement the methods added in the later versions
+    fn require_api_version(&self, at: &BlockId<Block>, version: u32) -> Result<(), CallError> {
+        let supported = self
+            .client
+            .runtime_api()
+            .has_api_with::<dyn FusoVerifierRuntimeApi<Block, AccountId, Balance>, _>(at, |v| {
+                v >= version
+            })
+            .unwrap_or(false);
+        if supported {
+            Ok(())
+        } else {
+            Err(CallError::Custom(ErrorObject::owned(
+                ErrorCode::MethodNotFound.code(),
+                "The method isn't supported by the runtime of the block",
+                None::<()>,
+            )))
+        }
+    }
+}
+
 impl<C, Block, AccountId, Balance>
     FusoVerifierApiServer<<Block as BlockT>::Hash, AccountId, Balance>
     for FusoVerifier<C, (Block, AccountId, Balance)>
@@ -115,4 +190,145 @@ where
             .into()
         })
     }
+
+    fn unbonding_chunks_of(
+        &self,
+        staker: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(u32, NumberOrHex)>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.require_api_version(&block_hash, 2)?;
+        let chunks = api.unbonding_chunks_of(&block_hash, staker).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(102i32).code(),
+                "Unable to query unbonding chunks",
+                Some(format!("{:?}", e)),
+            ))
+        })?;
+        chunks
+            .into_iter()
+            .map(|(unlock_at, amount)| {
+                amount
+                    .try_into()
+                    .map(|amount| (unlock_at, amount))
+                    .map_err(|_| {
+                        CallError::Custom(ErrorObject::owned(
+                            ErrorCode::InvalidParams.code(),
+                            "doesn't fit in NumberOrHex representation",
+                            None::<()>,
+                        ))
+                        .into()
+                    })
+            })
+            .collect()
+    }
+
+    fn root_checkpoints_of(
+        &self,
+        dominator: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(u64, u32, H256)>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.require_api_version(&block_hash, 2)?;
+        api.root_checkpoints_of(&block_hash, dominator)
+            .map(|checkpoints| {
+                checkpoints
+                    .into_iter()
+                    .map(|(event_id, block_number, root)| (event_id, block_number, root.into()))
+                    .collect()
+            })
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::ServerError(103i32).code(),
+                    "Unable to query root checkpoints",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn proof_of_reserves_of(
+        &self,
+        dominator: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<ReservesSnapshot> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.require_api_version(&block_hash, 2)?;
+        let (totals, root, event_id, block_number) = api
+            .proof_of_reserves_of(&block_hash, dominator)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::ServerError(104i32).code(),
+                    "Unable to query proof of reserves",
+                    Some(format!("{:?}", e)),
+                ))
+            })?;
+        let totals = totals
+            .into_iter()
+            .map(|(token_id, total)| {
+                total
+                    .try_into()
+                    .map(|total| (token_id, total))
+                    .map_err(|_| {
+                        CallError::Custom(ErrorObject::owned(
+                            ErrorCode::InvalidParams.code(),
+                            "doesn't fit in NumberOrHex representation",
+                            None::<()>,
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ReservesSnapshot {
+            totals,
+            root: root.into(),
+            event_id,
+            block_number,
+        })
+    }
+
+    fn dominator_of_name(
+        &self,
+        name: String,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<AccountId>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.require_api_version(&block_hash, 2)?;
+        api.dominator_of_name(&block_hash, name.into_bytes())
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::ServerError(105i32).code(),
+                    "Unable to query dominator by name",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
+
+    fn x25519_keys_of(
+        &self,
+        dominator: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<(u32, Bytes, u32)>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        self.require_api_version(&block_hash, 2)?;
+        api.x25519_keys_of(&block_hash, dominator)
+            .map(|keys| {
+                keys.into_iter()
+                    .map(|(version, key, activate_at)| (version, key.into(), activate_at))
+                    .collect()
+            })
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::ServerError(106i32).code(),
+                    "Unable to query x25519 keys",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
 }
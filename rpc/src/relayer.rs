@@ -0,0 +1,334 @@
+// Copyright 2021-2023 UINB Technologies Pte. Ltd.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::broker::{self, OrderEvent, OrderState};
+use jsonrpsee::{
+    core::client::{ClientT, Subscription, SubscriptionClientT},
+    rpc_params,
+    types::error::{ErrorCode, ErrorObject, SubscriptionClosed},
+    ws_client::{WsClient, WsClientBuilder},
+    ws_server::SubscriptionSink,
+};
+use sp_core::{crypto::AccountId32, Bytes};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::RwLock;
+
+type AccountId = AccountId32;
+
+/// the upstream subscription is dropped by the client once the buffered events exceed this
+const MAX_BUFFERED_EVENTS: usize = 1024;
+const MAX_RESUBSCRIBE_RETRIES: u32 = 5;
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_secs(2);
+/// fail fast so the secondary endpoint is tried before the client gives up
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RelayError {
+    InvalidEndpoint,
+    Unreachable(String),
+    Rejected(String),
+}
+
+/// the websocket connections to the provers, reconnecting once the endpoints change or drop
+#[derive(Clone, Default)]
+pub struct Relayer {
+    connections: Arc<RwLock<HashMap<AccountId, (Vec<u8>, Arc<WsClient>)>>>,
+}
+
+impl Relayer {
+    /// reuse the live connection if its endpoint is still listed, otherwise try the endpoints
+    /// in order so the secondary takes over once the primary is down
+    pub async fn connect(
+        &self,
+        prover: &AccountId,
+        endpoints: &[Vec<u8>],
+    ) -> Result<Arc<WsClient>, RelayError> {
+        if let Some((cached, client)) = self.connections.read().await.get(prover) {
+            if endpoints.contains(cached) && client.is_connected() {
+                return Ok(client.clone());
+            }
+        }
+        let mut last_error = RelayError::InvalidEndpoint;
+        for endpoint in endpoints {
+            match Self::open(endpoint).await {
+                Ok(client) => {
+                    self.connections
+                        .write()
+                        .await
+                        .insert(prover.clone(), (endpoint.clone(), client.clone()));
+                    return Ok(client);
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn open(endpoint: &[u8]) -> Result<Arc<WsClient>, RelayError> {
+        let url = std::str::from_utf8(endpoint).map_err(|_| RelayError::InvalidEndpoint)?;
+        WsClientBuilder::default()
+            .connection_timeout(CONNECT_TIMEOUT)
+            .max_notifs_per_subscription(MAX_BUFFERED_EVENTS)
+            .build(url)
+            .await
+            .map(Arc::new)
+            .map_err(|e| RelayError::Unreachable(e.to_string()))
+    }
+
+    /// drop the connection of the prover, e.g. its endpoints have been updated
+    pub async fn disconnect(&self, prover: &AccountId) {
+        self.connections.write().await.remove(prover);
+    }
+
+    /// forward the signed command to the prover and return the order id
+    pub async fn trade(
+        &self,
+        prover: &AccountId,
+        endpoints: &[Vec<u8>],
+        payload: Vec<u8>,
+        relayer: AccountId,
+        signature: Vec<u8>,
+    ) -> Result<String, RelayError> {
+        let client = self.connect(prover, endpoints).await?;
+        client
+            .request(
+                "prover_trade",
+                rpc_params![Bytes(payload), relayer, Bytes(signature)],
+            )
+            .await
+            .map_err(|e| RelayError::Rejected(e.to_string()))
+    }
+
+    pub async fn query_orders(
+        &self,
+        prover: &AccountId,
+        endpoints: &[Vec<u8>],
+        account_id: AccountId,
+        orders: Vec<(u32, u32, String)>,
+        expire_at: u64,
+        signature: Bytes,
+    ) -> Result<Vec<OrderState>, RelayError> {
+        let client = self.connect(prover, endpoints).await?;
+        client
+            .request(
+                "prover_queryOrders",
+                rpc_params![account_id, orders, expire_at, signature],
+            )
+            .await
+            .map_err(|e| RelayError::Rejected(e.to_string()))
+    }
+
+    pub async fn subscribe_order_events(
+        &self,
+        prover: &AccountId,
+        endpoints: &[Vec<u8>],
+        account_id: AccountId,
+        expire_at: u64,
+        signature: Bytes,
+    ) -> Result<Subscription<OrderEvent>, RelayError> {
+        let client = self.connect(prover, endpoints).await?;
+        client
+            .subscribe(
+                "prover_subscribeOrderEvents",
+                rpc_params![account_id, expire_at, signature],
+                "prover_unsubscribeOrderEvents",
+            )
+            .await
+            .map_err(|e| RelayError::Rejected(e.to_string()))
+    }
+
+    /// push the order events to the client until it unsubscribes, the upstream dropped by the
+    /// prover or for lagging behind is resubscribed with backoff while the client's signature is
+    /// fresh, otherwise the client is asked to resubscribe with a new one
+    pub async fn pipe_order_events(
+        &self,
+        mut sink: SubscriptionSink,
+        mut upstream: Subscription<OrderEvent>,
+        prover: &AccountId,
+        endpoints: &[Vec<u8>],
+        account_id: AccountId,
+        expire_at: u64,
+        signature: Bytes,
+    ) {
+        let mut retries = 0u32;
+        loop {
+            while let Some(Ok(event)) = upstream.next().await {
+                retries = 0;
+                match sink.send(&event) {
+                    Ok(true) => {}
+                    _ => return,
+                }
+            }
+            if sink.is_closed() || retries >= MAX_RESUBSCRIBE_RETRIES {
+                return;
+            }
+            retries += 1;
+            tokio::time::sleep(RESUBSCRIBE_BACKOFF * retries).await;
+            if !broker::is_fresh(expire_at, broker::now_millis()) {
+                let _ = sink.close(SubscriptionClosed::Failed(ErrorObject::owned(
+                    ErrorCode::ServerError(93110i32).code(),
+                    "The signature has expired, resubscribe with a fresh one.",
+                    None::<()>,
+                )));
+                return;
+            }
+            if let Ok(resubscribed) = self
+                .subscribe_order_events(
+                    prover,
+                    endpoints,
+                    account_id.clone(),
+                    expire_at,
+                    signature.clone(),
+                )
+                .await
+            {
+                upstream = resubscribed;
+            }
+        }
+    }
+
+    /// drop the broken connections, they will be rebuilt on the next request
+    pub async fn prune(&self) {
+        self.connections
+            .write()
+            .await
+            .retain(|_, (_, client)| client.is_connected());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Compact;
+    use jsonrpsee::{types::SubscriptionEmptyError, ws_server::WsServerBuilder, RpcModule};
+    use sp_keyring::AccountKeyring;
+
+    #[tokio::test]
+    async fn relay_to_mock_prover_should_work() {
+        let server = WsServerBuilder::default()
+            .build("127.0.0.1:0")
+            .await
+            .unwrap();
+        let endpoint = format!("ws://{}", server.local_addr().unwrap());
+        let mut module = RpcModule::new(());
+        module
+            .register_method("prover_trade", |params, _| {
+                let (payload, _, _): (Bytes, AccountId, Bytes) = params.parse()?;
+                Ok(format!("{}", payload.0.len()))
+            })
+            .unwrap();
+        module
+            .register_method("prover_queryOrders", |params, _| {
+                let (_, orders, _, _): (AccountId, Vec<(u32, u32, String)>, u64, Bytes) =
+                    params.parse()?;
+                Ok(orders
+                    .into_iter()
+                    .map(|(base, quote, order_id)| OrderState {
+                        order_id,
+                        direction: 0,
+                        base,
+                        quote,
+                        state: 0,
+                        unfilled: Compact(0),
+                        total: Compact(0),
+                        price: Compact(0),
+                        filled_quote: Compact(0),
+                        update_at: 0,
+                    })
+                    .collect::<Vec<_>>())
+            })
+            .unwrap();
+        module
+            .register_subscription(
+                "prover_subscribeOrderEvents",
+                "prover_orderEvent",
+                "prover_unsubscribeOrderEvents",
+                |params, mut sink, _| {
+                    let (account_id, _, _): (AccountId, u64, Bytes) =
+                        params.parse().map_err(|_| SubscriptionEmptyError)?;
+                    sink.accept()?;
+                    let _ = sink.send(&OrderEvent {
+                        order_id: "7".to_string(),
+                        account_id,
+                        base: 1,
+                        quote: 0,
+                        state: 0,
+                        filled: Compact(0),
+                        price: Compact(0),
+                        update_at: 0,
+                    });
+                    Ok(())
+                },
+            )
+            .unwrap();
+        let _handle = server.start(module).unwrap();
+
+        let prover: AccountId = AccountKeyring::Alice.into();
+        let relayer = Relayer::default();
+        let endpoints = vec![b"ws://127.0.0.1:1".to_vec(), endpoint.as_bytes().to_vec()];
+        let order_id = relayer
+            .trade(
+                &prover,
+                &endpoints,
+                vec![1; 7],
+                AccountKeyring::Bob.into(),
+                vec![0; 64],
+            )
+            .await
+            .unwrap();
+        assert_eq!(order_id, "7");
+        let states = relayer
+            .query_orders(
+                &prover,
+                &endpoints,
+                AccountKeyring::Ferdie.into(),
+                vec![(1, 0, "7".to_string())],
+                1_000,
+                Bytes(vec![0; 64]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].order_id, "7");
+        let mut events = relayer
+            .subscribe_order_events(
+                &prover,
+                &endpoints,
+                AccountKeyring::Ferdie.into(),
+                1_000,
+                Bytes(vec![0; 64]),
+            )
+            .await
+            .unwrap();
+        assert_eq!(events.next().await.unwrap().unwrap().order_id, "7");
+        assert_eq!(
+            relayer.connections.read().await.get(&prover).unwrap().0,
+            endpoint.as_bytes()
+        );
+        relayer.disconnect(&prover).await;
+        assert!(relayer.connections.read().await.is_empty());
+        assert_eq!(
+            relayer
+                .trade(
+                    &prover,
+                    &[b"\xff".to_vec()],
+                    vec![],
+                    AccountKeyring::Bob.into(),
+                    vec![]
+                )
+                .await,
+            Err(RelayError::InvalidEndpoint)
+        );
+    }
+}
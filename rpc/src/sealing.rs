@@ -0,0 +1,158 @@
+// Copyright 2021-2023 UINB Technologies Pte. Ltd.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use chacha20poly1305::{
+    aead::{Aead, NewAead, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand_core::OsRng;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+pub const X25519_KEY_LEN: usize = 32;
+
+const VERSION_LEN: usize = 4;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SealError {
+    InvalidKey,
+    InvalidPayload,
+    Crypto,
+}
+
+/// the sealed payload is `key_version(u32 le) ++ ephemeral_pubkey ++ ciphertext ++ tag`,
+/// the key and nonce are derived from the ephemeral DH so only the dominator could open it,
+/// the key version is authenticated as the associated data
+pub fn seal(version: u32, receiver: &[u8], payload: &[u8]) -> Result<Vec<u8>, SealError> {
+    let receiver: [u8; X25519_KEY_LEN] = receiver.try_into().map_err(|_| SealError::InvalidKey)?;
+    let ephemeral = EphemeralSecret::new(OsRng);
+    let ephemeral_pubkey = PublicKey::from(&ephemeral);
+    let shared = ephemeral.diffie_hellman(&PublicKey::from(receiver));
+    let (key, nonce) = derive(shared.as_bytes(), ephemeral_pubkey.as_bytes(), &receiver);
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: payload,
+                aad: &version.to_le_bytes(),
+            },
+        )
+        .map_err(|_| SealError::Crypto)?;
+    let mut sealed = Vec::with_capacity(VERSION_LEN + X25519_KEY_LEN + ciphertext.len());
+    sealed.extend_from_slice(&version.to_le_bytes());
+    sealed.extend_from_slice(ephemeral_pubkey.as_bytes());
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// open the sealed payload with the x25519 secret of the dominator
+pub fn open(secret: &[u8], sealed: &[u8]) -> Result<(u32, Vec<u8>), SealError> {
+    let secret: [u8; X25519_KEY_LEN] = secret.try_into().map_err(|_| SealError::InvalidKey)?;
+    let version = key_version(sealed).ok_or(SealError::InvalidPayload)?;
+    let secret = StaticSecret::from(secret);
+    let receiver = PublicKey::from(&secret);
+    let mut ephemeral_pubkey = [0u8; X25519_KEY_LEN];
+    ephemeral_pubkey.copy_from_slice(&sealed[VERSION_LEN..VERSION_LEN + X25519_KEY_LEN]);
+    let shared = secret.diffie_hellman(&PublicKey::from(ephemeral_pubkey));
+    let (key, nonce) = derive(shared.as_bytes(), &ephemeral_pubkey, receiver.as_bytes());
+    let payload = ChaCha20Poly1305::new(Key::from_slice(&key))
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &sealed[VERSION_LEN + X25519_KEY_LEN..],
+                aad: &version.to_le_bytes(),
+            },
+        )
+        .map_err(|_| SealError::Crypto)?;
+    Ok((version, payload))
+}
+
+/// the version of the dominator key which the payload is sealed to, without opening it
+pub fn key_version(sealed: &[u8]) -> Option<u32> {
+    if sealed.len() < VERSION_LEN + X25519_KEY_LEN + TAG_LEN {
+        return None;
+    }
+    let mut version = [0u8; VERSION_LEN];
+    version.copy_from_slice(&sealed[..VERSION_LEN]);
+    Some(u32::from_le_bytes(version))
+}
+
+fn derive(
+    shared: &[u8; X25519_KEY_LEN],
+    ephemeral_pubkey: &[u8; X25519_KEY_LEN],
+    receiver: &[u8; X25519_KEY_LEN],
+) -> ([u8; 32], [u8; NONCE_LEN]) {
+    let key = sp_core::blake2_256(&[&shared[..], &ephemeral_pubkey[..], &receiver[..]].concat());
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce.copy_from_slice(
+        &sp_core::blake2_256(&[&ephemeral_pubkey[..], &receiver[..]].concat())[..NONCE_LEN],
+    );
+    (key, nonce)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair() -> ([u8; X25519_KEY_LEN], [u8; X25519_KEY_LEN]) {
+        let secret = StaticSecret::new(OsRng);
+        let public = PublicKey::from(&secret);
+        (secret.to_bytes(), public.to_bytes())
+    }
+
+    #[test]
+    fn seal_and_open_should_work() {
+        let (secret, public) = keypair();
+        let sealed = seal(7, &public, b"hello").unwrap();
+        assert_eq!(sealed.len(), VERSION_LEN + X25519_KEY_LEN + 5 + TAG_LEN);
+        assert_eq!(key_version(&sealed), Some(7));
+        assert_eq!(open(&secret, &sealed), Ok((7, b"hello".to_vec())));
+        assert_eq!(seal(7, &public[1..], b"hello"), Err(SealError::InvalidKey));
+    }
+
+    #[test]
+    fn tampered_payload_should_not_open() {
+        let (secret, public) = keypair();
+        let (other, _) = keypair();
+        let sealed = seal(1, &public, b"hello").unwrap();
+        assert_eq!(open(&other, &sealed), Err(SealError::Crypto));
+        let mut flipped = sealed.clone();
+        flipped[VERSION_LEN + X25519_KEY_LEN] ^= 1;
+        assert_eq!(open(&secret, &flipped), Err(SealError::Crypto));
+        let mut flipped = sealed.clone();
+        *flipped.last_mut().unwrap() ^= 1;
+        assert_eq!(open(&secret, &flipped), Err(SealError::Crypto));
+        // the version is bound to the ciphertext
+        let mut flipped = sealed.clone();
+        flipped[0] ^= 1;
+        assert_eq!(open(&secret, &flipped), Err(SealError::Crypto));
+        assert_eq!(
+            open(
+                &secret,
+                &sealed[..VERSION_LEN + X25519_KEY_LEN + TAG_LEN - 1]
+            ),
+            Err(SealError::InvalidPayload)
+        );
+    }
+
+    #[test]
+    fn key_version_should_be_parsed() {
+        let mut sealed = vec![0u8; VERSION_LEN + X25519_KEY_LEN + TAG_LEN];
+        sealed[..VERSION_LEN].copy_from_slice(&0x01020304u32.to_le_bytes());
+        assert_eq!(key_version(&sealed), Some(0x01020304));
+        assert_eq!(key_version(&sealed[..sealed.len() - 1]), None);
+        assert_eq!(key_version(&[]), None);
+    }
+}
@@ -12,13 +12,268 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+pub use fuso_token_runtime_api::FusoTokenRuntimeApi;
+
+use codec::Codec;
+use jsonrpsee::{
+    core::RpcResult,
+    proc_macros::rpc,
+    types::error::{CallError, ErrorCode, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+    generic::BlockId,
+    traits::{Block as BlockT, MaybeDisplay, Saturating},
+};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenBalance<TokenId> {
+    pub token_id: TokenId,
+    pub free: NumberOrHex,
+    pub reserved: NumberOrHex,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenInfo {
+    pub symbol: String,
+    pub contract: Bytes,
+    pub total: NumberOrHex,
+    pub stable: bool,
+    pub decimals: u8,
+    pub chain_id: u16,
+}
 
 #[rpc(client, server)]
-pub trait TokenApi<BlockHash, Account, Balance> {
+pub trait TokenApi<BlockHash, AccountId, TokenId> {
     #[method(name = "token_freeBalance")]
-    fn free_balance(&self, who: Account, at: Option<BlockHash>) -> RpcResult<Balance>;
+    fn free_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<NumberOrHex>;
+
+    #[method(name = "token_reservedBalance")]
+    fn reserved_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<NumberOrHex>;
 
     #[method(name = "token_totalBalance")]
-    fn total_balance(&self, who: Account, at: Option<BlockHash>) -> RpcResult<Balance>;
+    fn total_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<NumberOrHex>;
+
+    #[method(name = "token_balancesOf")]
+    fn balances_of(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<TokenBalance<TokenId>>>;
+
+    #[method(name = "token_tokenInfo")]
+    fn token_info(&self, token: TokenId, at: Option<BlockHash>) -> RpcResult<Option<TokenInfo>>;
+
+    #[method(name = "token_tokens")]
+    fn tokens(&self, at: Option<BlockHash>) -> RpcResult<Vec<TokenId>>;
+
+    #[method(name = "token_tokenByContract")]
+    fn token_by_contract(
+        &self,
+        chain_id: u16,
+        contract: Bytes,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<TokenId>>;
+}
+
+pub struct FusoToken<C, B> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> FusoToken<C, B> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, AccountId, TokenId, Balance> FusoToken<C, (Block, AccountId, TokenId, Balance)>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: FusoTokenRuntimeApi<Block, AccountId, TokenId, Balance>,
+    Block: BlockT,
+    AccountId: Codec + MaybeDisplay + Send + Sync + 'static,
+    TokenId: Codec + MaybeDisplay + Send + Sync + 'static,
+    Balance: Codec + MaybeDisplay + Send + Sync + 'static,
+{
+    fn balance_of(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<(Balance, Balance), CallError> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.balance_of(&block_hash, token, who).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(201i32).code(),
+                "Unable to query balance",
+                Some(format!("{:?}", e)),
+            ))
+        })
+    }
+}
+
+fn to_number_or_hex<Balance: TryInto<NumberOrHex>>(
+    balance: Balance,
+) -> Result<NumberOrHex, CallError> {
+    balance.try_into().map_err(|_| {
+        CallError::Custom(ErrorObject::owned(
+            ErrorCode::InvalidParams.code(),
+            "doesn't fit in NumberOrHex representation",
+            None::<()>,
+        ))
+    })
+}
+
+impl<C, Block, AccountId, TokenId, Balance>
+    TokenApiServer<<Block as BlockT>::Hash, AccountId, TokenId>
+    for FusoToken<C, (Block, AccountId, TokenId, Balance)>
+where
+    C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: FusoTokenRuntimeApi<Block, AccountId, TokenId, Balance>,
+    Block: BlockT,
+    AccountId: Codec + MaybeDisplay + Send + Sync + 'static,
+    TokenId: Codec + MaybeDisplay + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+    Balance: Codec + MaybeDisplay + Saturating + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+    fn free_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<NumberOrHex> {
+        let (free, _) = self.balance_of(token, who, at)?;
+        Ok(to_number_or_hex(free)?)
+    }
+
+    fn reserved_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<NumberOrHex> {
+        let (_, reserved) = self.balance_of(token, who, at)?;
+        Ok(to_number_or_hex(reserved)?)
+    }
+
+    fn total_balance(
+        &self,
+        token: TokenId,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<NumberOrHex> {
+        let (free, reserved) = self.balance_of(token, who, at)?;
+        Ok(to_number_or_hex(free.saturating_add(reserved))?)
+    }
+
+    fn balances_of(
+        &self,
+        who: AccountId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Vec<TokenBalance<TokenId>>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let balances = api.balances_of(&block_hash, who).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(202i32).code(),
+                "Unable to query balances",
+                Some(format!("{:?}", e)),
+            ))
+        })?;
+        balances
+            .into_iter()
+            .map(|(token_id, free, reserved)| {
+                Ok(TokenBalance {
+                    token_id,
+                    free: to_number_or_hex(free)?,
+                    reserved: to_number_or_hex(reserved)?,
+                })
+            })
+            .collect()
+    }
+
+    fn token_info(
+        &self,
+        token: TokenId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<TokenInfo>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let info = api.token_info(&block_hash, token).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(203i32).code(),
+                "Unable to query token info",
+                Some(format!("{:?}", e)),
+            ))
+        })?;
+        match info {
+            Some((symbol, contract, total, stable, decimals, chain_id)) => Ok(Some(TokenInfo {
+                symbol: String::from_utf8_lossy(&symbol).into_owned(),
+                contract: contract.into(),
+                total: to_number_or_hex(total)?,
+                stable,
+                decimals,
+                chain_id,
+            })),
+            None => Ok(None),
+        }
+    }
+
+    fn tokens(&self, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Vec<TokenId>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.tokens(&block_hash).map_err(|e| {
+            CallError::Custom(ErrorObject::owned(
+                ErrorCode::ServerError(204i32).code(),
+                "Unable to query tokens",
+                Some(format!("{:?}", e)),
+            ))
+            .into()
+        })
+    }
+
+    fn token_by_contract(
+        &self,
+        chain_id: u16,
+        contract: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> RpcResult<Option<TokenId>> {
+        let api = self.client.runtime_api();
+        let block_hash = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.token_by_contract(&block_hash, chain_id, contract.0)
+            .map_err(|e| {
+                CallError::Custom(ErrorObject::owned(
+                    ErrorCode::ServerError(205i32).code(),
+                    "Unable to query token by contract",
+                    Some(format!("{:?}", e)),
+                ))
+                .into()
+            })
+    }
 }
@@ -0,0 +1,43 @@
+// Copyright 2021-2023 UINB Technologies Pte. Ltd.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_runtime::{sp_std::vec::Vec, traits::MaybeDisplay};
+
+sp_api::decl_runtime_apis! {
+    /// API to interact with pallet-fuso-token
+    pub trait FusoTokenRuntimeApi<AccountId, TokenId, Balance>
+    where
+        AccountId: Codec + MaybeDisplay,
+        TokenId: Codec + MaybeDisplay,
+        Balance: Codec + MaybeDisplay,
+    {
+        /// the free and reserved balances of an account, the native token is included
+        fn balance_of(token: TokenId, who: AccountId) -> (Balance, Balance);
+
+        /// the non-zero balances of an account as `(token, free, reserved)`
+        fn balances_of(who: AccountId) -> Vec<(TokenId, Balance, Balance)>;
+
+        /// the issued token as `(symbol, contract, total, stable, decimals, chain_id)`
+        fn token_info(token: TokenId) -> Option<(Vec<u8>, Vec<u8>, Balance, bool, u8, u16)>;
+
+        /// the native token followed by the issued tokens
+        fn tokens() -> Vec<TokenId>;
+
+        /// the token associated with the contract on the chain
+        fn token_by_contract(chain_id: u16, contract: Vec<u8>) -> Option<TokenId>;
+    }
+}
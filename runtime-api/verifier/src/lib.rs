@@ -15,10 +15,12 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
-use sp_runtime::traits::MaybeDisplay;
+use sp_runtime::{sp_std::vec::Vec, traits::MaybeDisplay};
 
 sp_api::decl_runtime_apis! {
-    /// API to interact with pallet-fuso-verifier
+    /// API to interact with pallet-fuso-verifier, the methods after `pending_shares_of_dominator`
+    /// are added in the version 2
+    #[api_version(2)]
     pub trait FusoVerifierRuntimeApi<AccountId, Balance>
     where
         AccountId: Codec + MaybeDisplay,
@@ -27,5 +29,20 @@ sp_api::decl_runtime_apis! {
         fn current_season_of_dominator(dominator: AccountId) -> u32;
 
         fn pending_shares_of_dominator(dominator: AccountId, who: AccountId) -> Balance;
+
+        /// the pending unstakings of a staker as `(unlock_at, amount)`
+        fn unbonding_chunks_of(staker: AccountId) -> Vec<(u32, Balance)>;
+
+        /// the historical merkle roots of a dominator as `(event_id, block_number, root)`
+        fn root_checkpoints_of(dominator: AccountId) -> Vec<(u64, u32, [u8; 32])>;
+
+        /// the authorized totals by token with the latest `(root, event_id, block_number)`
+        fn proof_of_reserves_of(dominator: AccountId) -> (Vec<(u32, Balance)>, [u8; 32], u64, u32);
+
+        /// the dominator registered with the name, case-insensitively
+        fn dominator_of_name(name: Vec<u8>) -> Option<AccountId>;
+
+        /// the current x25519 key and the rotating one as `(version, key, activate_at)`
+        fn x25519_keys_of(dominator: AccountId) -> Vec<(u32, Vec<u8>, u32)>;
     }
 }